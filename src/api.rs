@@ -0,0 +1,137 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use futures_util::stream::{BoxStream, StreamExt};
+
+use crate::{
+    Client, CreatedUser, Error, Permission, Role, Tenant, TenantId, TenantRequest, User, UserId,
+    UserListConfig, UserRequest,
+};
+
+/// The core operations of the Frontegg API.
+///
+/// [`Client`] is the production implementation of this trait. Code that
+/// depends on the Frontegg API can be written generically over this trait so
+/// that it can be tested against a fake implementation rather than a live or
+/// mock server.
+///
+/// The trait deliberately covers only a minimal subset of the operations
+/// supported by [`Client`]: the tenant, user, role, and permission operations
+/// that most applications depend on. Keeping it small keeps fakes cheap to
+/// write. Operations that are not part of the trait, like API tokens, domains,
+/// and SSO configuration, must be called on [`Client`] directly. Unlike the
+/// corresponding [`Client`] methods, which accept anything convertible to a
+/// [`TenantId`] or [`UserId`], the trait's methods take these types directly
+/// so that the trait remains object safe.
+///
+/// See the corresponding methods on [`Client`] for details on each operation.
+#[async_trait]
+pub trait FronteggApi: Send + Sync {
+    /// Lists all tenants.
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, Error>;
+
+    /// Creates a new tenant.
+    async fn create_tenant(&self, tenant: &TenantRequest<'_>) -> Result<Tenant, Error>;
+
+    /// Gets a tenant by ID.
+    async fn get_tenant(&self, id: TenantId) -> Result<Tenant, Error>;
+
+    /// Deletes a tenant.
+    async fn delete_tenant(&self, id: TenantId) -> Result<(), Error>;
+
+    /// Sets a tenant's metadata.
+    async fn set_tenant_metadata(
+        &self,
+        id: TenantId,
+        metadata: &serde_json::Value,
+    ) -> Result<Tenant, Error>;
+
+    /// Removes a key from a tenant's metadata.
+    async fn delete_tenant_metadata(&self, id: TenantId, key: &str) -> Result<Tenant, Error>;
+
+    /// Lists users, either for all tenants or for a single tenant.
+    fn list_users(&self, config: UserListConfig) -> BoxStream<'_, Result<User, Error>>;
+
+    /// Creates a new user.
+    async fn create_user(&self, user: &UserRequest<'_>) -> Result<CreatedUser, Error>;
+
+    /// Gets a user by ID.
+    async fn get_user(&self, id: UserId) -> Result<User, Error>;
+
+    /// Deletes a user by ID.
+    async fn delete_user(&self, id: UserId) -> Result<(), Error>;
+
+    /// Lists all roles.
+    async fn list_roles(&self) -> Result<Vec<Role>, Error>;
+
+    /// Lists all permissions.
+    async fn list_permissions(&self) -> Result<Vec<Permission>, Error>;
+}
+
+#[async_trait]
+impl FronteggApi for Client {
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, Error> {
+        Client::list_tenants(self).await
+    }
+
+    async fn create_tenant(&self, tenant: &TenantRequest<'_>) -> Result<Tenant, Error> {
+        Client::create_tenant(self, tenant).await
+    }
+
+    async fn get_tenant(&self, id: TenantId) -> Result<Tenant, Error> {
+        Client::get_tenant(self, id).await
+    }
+
+    async fn delete_tenant(&self, id: TenantId) -> Result<(), Error> {
+        Client::delete_tenant(self, id).await
+    }
+
+    async fn set_tenant_metadata(
+        &self,
+        id: TenantId,
+        metadata: &serde_json::Value,
+    ) -> Result<Tenant, Error> {
+        Client::set_tenant_metadata(self, id, metadata).await
+    }
+
+    async fn delete_tenant_metadata(&self, id: TenantId, key: &str) -> Result<Tenant, Error> {
+        Client::delete_tenant_metadata(self, id, key).await
+    }
+
+    fn list_users(&self, config: UserListConfig) -> BoxStream<'_, Result<User, Error>> {
+        Client::list_users(self, config).boxed()
+    }
+
+    async fn create_user(&self, user: &UserRequest<'_>) -> Result<CreatedUser, Error> {
+        Client::create_user(self, user).await
+    }
+
+    async fn get_user(&self, id: UserId) -> Result<User, Error> {
+        Client::get_user(self, id).await
+    }
+
+    async fn delete_user(&self, id: UserId) -> Result<(), Error> {
+        Client::delete_user(self, id).await
+    }
+
+    async fn list_roles(&self) -> Result<Vec<Role>, Error> {
+        Client::list_roles(self).await
+    }
+
+    async fn list_permissions(&self) -> Result<Vec<Permission>, Error> {
+        Client::list_permissions(self).await
+    }
+}
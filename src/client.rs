@@ -13,22 +13,45 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
-use reqwest::{Method, Url};
+use async_stream::try_stream;
+use futures_core::Stream;
+use reqwest::header::{HeaderMap, ETAG, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode, Url};
 use reqwest_middleware::{ClientWithMiddleware, RequestBuilder};
+use reqwest_retry::Retryable;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use tokio::sync::Mutex;
+use tracing::{debug, warn};
 
-use crate::error::ApiError;
+use crate::config::ENDPOINT_ENV_VAR;
+use crate::error::{ApiError, EnvError};
+use crate::retry::AttemptCounter;
+use crate::secret::SecretString;
+use crate::serde::Paginated;
+use crate::token::{StoredToken, TokenKey, TokenStore};
+use crate::util;
 use crate::{ClientBuilder, ClientConfig, Error};
 
+pub mod api_tokens;
+pub mod applications;
+pub mod audits;
+pub mod domains;
 pub mod roles;
+pub mod scoped;
+pub mod sso;
 pub mod tenants;
 pub mod users;
+pub mod vendors;
+pub mod webhooks;
 
-const AUTH_VENDOR_PATH: [&str; 2] = ["auth", "vendor"];
+pub(crate) const AUTH_VENDOR_PATH: [&str; 2] = ["auth", "vendor"];
 
 /// An API client for Frontegg.
 ///
@@ -37,14 +60,108 @@ const AUTH_VENDOR_PATH: [&str; 2] = ["auth", "vendor"];
 /// shared by all threads.
 ///
 /// [`Arc`]: std::sync::Arc
-#[derive(Debug)]
 pub struct Client {
     pub(crate) client_retryable: ClientWithMiddleware,
     pub(crate) client_non_retryable: ClientWithMiddleware,
     pub(crate) client_id: String,
-    pub(crate) secret_key: String,
+    pub(crate) secret_key: SecretString,
     pub(crate) vendor_endpoint: Url,
-    pub(crate) auth: Mutex<Option<Auth>>,
+    pub(crate) auth_path: Vec<String>,
+    pub(crate) default_skip_invite_email: bool,
+    pub(crate) bulk_concurrency: usize,
+    pub(crate) max_error_body_len: usize,
+    pub(crate) max_response_size: usize,
+    pub(crate) token_store: Option<Arc<dyn TokenStore>>,
+    pub(crate) auth: std::sync::Mutex<Option<Auth>>,
+    pub(crate) auth_refresh: Mutex<()>,
+    pub(crate) last_rate_limit: std::sync::Mutex<Option<RateLimit>>,
+}
+
+/// The order in which to sort the results of a list operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Ascending order.
+    Asc,
+    /// Descending order.
+    Desc,
+}
+
+impl SortOrder {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Rate limit information reported by the Frontegg API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The maximum number of requests permitted in the current window.
+    pub limit: u64,
+    /// The number of requests remaining in the current window.
+    pub remaining: u64,
+    /// The number of seconds until the current window resets, if reported.
+    pub reset: Option<u64>,
+}
+
+impl RateLimit {
+    fn from_headers(headers: &HeaderMap) -> Option<RateLimit> {
+        fn parse(headers: &HeaderMap, name: &str) -> Option<u64> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        Some(RateLimit {
+            limit: parse(headers, "x-ratelimit-limit")?,
+            remaining: parse(headers, "x-ratelimit-remaining")?,
+            reset: parse(headers, "x-ratelimit-reset"),
+        })
+    }
+}
+
+/// A reference to an operation that Frontegg has accepted for asynchronous
+/// processing but not yet completed.
+///
+/// Frontegg indicates such operations with a `202 Accepted` response, whose
+/// body describes the job performing the operation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncOperation {
+    /// The ID of the job performing the operation, if Frontegg reported one.
+    #[serde(default, alias = "id")]
+    pub job_id: Option<String>,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// A decoded response to an API call.
+struct Decoded<T> {
+    body: T,
+    status: StatusCode,
+    headers: HeaderMap,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("client_retryable", &self.client_retryable)
+            .field("client_non_retryable", &self.client_non_retryable)
+            .field("client_id", &self.client_id)
+            .field("secret_key", &self.secret_key)
+            .field("vendor_endpoint", &self.vendor_endpoint)
+            .field("auth_path", &self.auth_path)
+            .field("default_skip_invite_email", &self.default_skip_invite_email)
+            .field("bulk_concurrency", &self.bulk_concurrency)
+            .field("max_error_body_len", &self.max_error_body_len)
+            .field("max_response_size", &self.max_response_size)
+            .field("token_store", &self.token_store.is_some())
+            .field("auth", &self.auth)
+            .field("last_rate_limit", &self.last_rate_limit)
+            .finish()
+    }
 }
 
 impl Client {
@@ -53,21 +170,47 @@ impl Client {
         ClientBuilder::default().build(config)
     }
 
+    /// Creates a new `Client` configured from environment variables.
+    ///
+    /// The credentials are read as if via [`ClientConfig::from_env`], and the
+    /// vendor endpoint as if via
+    /// [`ClientBuilder::with_vendor_endpoint_from_env`].
+    pub fn from_env() -> Result<Client, EnvError> {
+        let config = ClientConfig::from_env()?;
+        let builder = ClientBuilder::default().with_vendor_endpoint_from_env()?;
+        builder.try_build(config).map_err(|e| EnvError::Invalid {
+            name: ENDPOINT_ENV_VAR.into(),
+            message: e.to_string(),
+        })
+    }
+
     /// Creates a builder for a `Client` that allows for customization of
     /// optional parameters.
     pub fn builder() -> ClientBuilder {
         ClientBuilder::default()
     }
 
+    /// Returns the rate limit information reported by the most recent API
+    /// response that included it.
+    ///
+    /// Returns `None` if no response has reported rate limit information yet.
+    /// The information is shared by all threads using the client, so it may
+    /// reflect a call made by another thread.
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self.last_rate_limit.lock().expect("lock poisoned")
+    }
+
     fn build_request<P>(&self, method: Method, path: P) -> RequestBuilder
     where
         P: IntoIterator,
         P::Item: AsRef<str>,
     {
+        // Append the path to any path prefix in the vendor endpoint, e.g.,
+        // when the API is served behind a reverse proxy.
         let mut url = self.vendor_endpoint.clone();
         url.path_segments_mut()
             .expect("builder validated URL can be a base")
-            .clear()
+            .pop_if_empty()
             .extend(path);
         match method {
             // GET and HEAD requests are idempotent and we can safely retry
@@ -79,16 +222,142 @@ impl Client {
         }
     }
 
+    /// Returns a stream over the items returned by a paginated API call.
+    ///
+    /// The `build` function is called to construct the request for each page.
+    /// Pagination parameters are added to the request automatically.
+    fn send_paginated_request<'a, T, F>(
+        &'a self,
+        page_size: u64,
+        build: F,
+    ) -> impl Stream<Item = Result<T, Error>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+        F: Fn() -> RequestBuilder + 'a,
+    {
+        try_stream! {
+            let mut page = 0;
+            loop {
+                let req = build().query(&[
+                    ("_limit", &*page_size.to_string()),
+                    ("_offset", &*page.to_string()),
+                ]);
+                let res: Paginated<T> = self.send_request(req).await?;
+                for item in res.items {
+                    yield item;
+                }
+                page += 1;
+                if page >= res.metadata.total_pages {
+                    break;
+                }
+            }
+        }
+    }
+
     async fn send_request<T>(&self, req: RequestBuilder) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let res = self.send_request_decoded(req).await?;
+        Ok(res.body)
+    }
+
+    /// Like [`Client::send_request`], but also returns the response status and
+    /// headers.
+    async fn send_request_decoded<T>(&self, req: RequestBuilder) -> Result<Decoded<T>, Error>
     where
         T: DeserializeOwned,
     {
         let token = self.ensure_authenticated().await?;
-        let req = req.bearer_auth(token);
-        self.send_unauthenticated_request(req).await
+        let req = req.bearer_auth(token.expose());
+        self.send_unauthenticated_request_decoded(req).await
+    }
+
+    /// Sends a request for an operation that Frontegg may accept for
+    /// asynchronous processing rather than complete immediately.
+    ///
+    /// Returns `None` if the operation completed, or a reference to the
+    /// operation if Frontegg responded with `202 Accepted`.
+    async fn send_request_or_accepted(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Option<AsyncOperation>, Error> {
+        let res: Decoded<serde_json::Value> = self.send_request_decoded(req).await?;
+        match (res.status, res.body) {
+            (StatusCode::ACCEPTED, serde_json::Value::Null) => Ok(Some(AsyncOperation::default())),
+            (StatusCode::ACCEPTED, body) => Ok(Some(serde_json::from_value(body)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Sends a conditional request that returns `None` if the resource's
+    /// entity tag still matches `etag`.
+    ///
+    /// Otherwise, returns the resource and its new entity tag, if the response
+    /// included one.
+    async fn send_conditional_request<T>(
+        &self,
+        req: RequestBuilder,
+        etag: Option<&str>,
+    ) -> Result<Option<(T, Option<String>)>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let req = match etag {
+            Some(etag) => req.header(IF_NONE_MATCH, etag),
+            None => req,
+        };
+        match self.send_request_decoded(req).await {
+            Ok(Decoded { body, headers, .. }) => {
+                let etag = headers
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(|etag| etag.to_string());
+                Ok(Some((body, etag)))
+            }
+            Err(Error::Api(e)) if e.status_code == StatusCode::NOT_MODIFIED => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
     async fn send_unauthenticated_request<T>(&self, req: RequestBuilder) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let res = self.send_unauthenticated_request_decoded(req).await?;
+        Ok(res.body)
+    }
+
+    async fn send_unauthenticated_request_decoded<T>(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Decoded<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let attempts = AttemptCounter::default();
+        let res = req.with_extension(attempts.clone()).send().await;
+        let attempts = attempts.get();
+        if attempts > 1 {
+            debug!(attempts, "frontegg request retried");
+        }
+        // The retry middleware gives up on a transient error only once the
+        // retry policy is exhausted.
+        let exhausted =
+            attempts > 1 && Retryable::from_reqwest_response(&res) == Some(Retryable::Transient);
+        match self.decode_response(res).await {
+            Err(e) if exhausted => Err(Error::RetriesExhausted {
+                attempts,
+                last: Box::new(e),
+            }),
+            res => res,
+        }
+    }
+
+    async fn decode_response<T>(
+        &self,
+        res: Result<reqwest::Response, reqwest_middleware::Error>,
+    ) -> Result<Decoded<T>, Error>
     where
         T: DeserializeOwned,
     {
@@ -101,29 +370,194 @@ impl Client {
             errors: Vec<String>,
         }
 
-        let res = req.send().await?;
+        let res = res?;
+        if let Some(rate_limit) = RateLimit::from_headers(res.headers()) {
+            *self.last_rate_limit.lock().expect("lock poisoned") = Some(rate_limit);
+        }
         let status_code = res.status();
         if status_code.is_success() {
-            Ok(res.json().await?)
+            let headers = res.headers().clone();
+            let body = self.read_body(res).await?;
+            // Successful responses may legitimately have no body, e.g., a
+            // `204 No Content` response to a delete. Treat them as `null`
+            // rather than attempting to parse zero bytes as JSON.
+            let body = if status_code == StatusCode::NO_CONTENT
+                || body.iter().all(u8::is_ascii_whitespace)
+            {
+                T::deserialize(serde_json::Value::Null)?
+            } else {
+                serde_json::from_slice(&body)?
+            };
+            Ok(Decoded {
+                body,
+                status: status_code,
+                headers,
+            })
         } else {
-            match res.json::<ErrorResponse>().await {
-                Ok(e) => {
-                    let mut messages = e.errors;
-                    messages.extend(e.message);
-                    Err(Error::Api(ApiError {
-                        status_code,
-                        messages,
-                    }))
+            // Keep the status of an oversized error response, like a proxy's
+            // HTML error page, by capturing only a prefix of its body.
+            let (body, _truncated) = self.read_body_prefix(res, self.max_response_size).await?;
+            let body = String::from_utf8_lossy(&body);
+            let max_len = self.max_error_body_len;
+            debug!(
+                status = %status_code,
+                body = %util::truncate(&body, max_len),
+                "frontegg api error",
+            );
+            let messages = match serde_json::from_str::<ErrorResponse>(&body) {
+                Ok(e) => e
+                    .errors
+                    .into_iter()
+                    .chain(e.message)
+                    .map(|m| util::truncate(&m, max_len).into_owned())
+                    .collect(),
+                Err(_) if body.trim().is_empty() => {
+                    vec!["unable to decode error details".into()]
                 }
-                Err(_) => Err(Error::Api(ApiError {
-                    status_code,
-                    messages: vec!["unable to decode error details".into()],
-                })),
+                Err(_) => vec![format!(
+                    "unable to decode error details: {}",
+                    util::truncate(&body, max_len)
+                )],
+            };
+            Err(Error::Api(ApiError::new(status_code, messages)))
+        }
+    }
+
+    /// Reads the body of a response, failing if it exceeds the maximum
+    /// response size.
+    async fn read_body(&self, res: reqwest::Response) -> Result<Vec<u8>, Error> {
+        let limit = self.max_response_size;
+        // Fail fast if the server announces an oversized body, but enforce
+        // the limit while reading too, as the announcement is optional.
+        if res.content_length().map_or(false, |len| len > limit as u64) {
+            return Err(Error::ResponseTooLarge { limit });
+        }
+        match self.read_body_prefix(res, limit).await? {
+            (_, true) => Err(Error::ResponseTooLarge { limit }),
+            (body, false) => Ok(body),
+        }
+    }
+
+    /// Reads at most `limit` bytes of the body of a response, and whether
+    /// the remainder of the body was discarded.
+    async fn read_body_prefix(
+        &self,
+        mut res: reqwest::Response,
+        limit: usize,
+    ) -> Result<(Vec<u8>, bool), Error> {
+        let mut body = Vec::new();
+        while let Some(chunk) = res.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                body.extend_from_slice(&chunk[..limit - body.len()]);
+                return Ok((body, true));
             }
+            body.extend_from_slice(&chunk);
         }
+        Ok((body, false))
+    }
+
+    /// Validates the client's credentials.
+    ///
+    /// Unlike other API calls, this method always makes an authentication
+    /// request, even if a previously issued token is still valid. This makes
+    /// it suitable for checking new credentials, e.g., after rotating the
+    /// secret key. Rejected credentials result in an [`Error::Api`] with a
+    /// `401 Unauthorized` status code.
+    pub async fn validate_credentials(&self) -> Result<(), Error> {
+        let _refresh = self.auth_refresh.lock().await;
+        self.authenticate().await?;
+        Ok(())
+    }
+
+    /// Checks that the client can authenticate with Frontegg.
+    ///
+    /// Useful as a readiness probe. Unlike
+    /// [`validate_credentials`](Client::validate_credentials), this method
+    /// reuses the cached authentication token if it is still valid, in which
+    /// case no network request is made.
+    pub async fn ping(&self) -> Result<(), Error> {
+        self.ensure_authenticated().await?;
+        Ok(())
     }
 
-    async fn ensure_authenticated(&self) -> Result<String, Error> {
+    async fn ensure_authenticated(&self) -> Result<SecretString, Error> {
+        if let Some(token) = self.cached_token(false) {
+            return Ok(token);
+        }
+        // Only one task refreshes the token at a time. If a refresh is
+        // already in flight and the cached token has not yet expired, use the
+        // cached token rather than waiting; otherwise wait for the refresh to
+        // complete and use its result.
+        let _refresh = match self.auth_refresh.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                if let Some(token) = self.cached_token(true) {
+                    return Ok(token);
+                }
+                self.auth_refresh.lock().await
+            }
+        };
+        // Another task may have refreshed the token while we were waiting.
+        if let Some(token) = self.cached_token(false) {
+            return Ok(token);
+        }
+        if let Some(token) = self.load_token().await {
+            // Use the token only until it is due for a refresh, as recorded
+            // when it was issued, rather than until it expires.
+            if OffsetDateTime::now_utc() < token.refresh_at {
+                *self.auth.lock().expect("lock poisoned") = Some(Auth {
+                    token: token.token.clone(),
+                    refresh_at: token.refresh_at.into(),
+                    expires_at: token.expires_at.into(),
+                });
+                return Ok(token.token);
+            }
+        }
+        self.authenticate().await
+    }
+
+    /// Returns the cached token if it is not due for a refresh or, if
+    /// `allow_stale` is set, if it has not yet expired.
+    fn cached_token(&self, allow_stale: bool) -> Option<SecretString> {
+        let auth = self.auth.lock().expect("lock poisoned");
+        let auth = auth.as_ref()?;
+        let deadline = match allow_stale {
+            false => auth.refresh_at,
+            true => auth.expires_at,
+        };
+        (SystemTime::now() < deadline).then(|| auth.token.clone())
+    }
+
+    fn token_key(&self) -> TokenKey {
+        TokenKey {
+            vendor_endpoint: self.vendor_endpoint.clone(),
+            client_id: self.client_id.clone(),
+        }
+    }
+
+    async fn load_token(&self) -> Option<StoredToken> {
+        let token_store = self.token_store.as_ref()?;
+        match token_store.load(&self.token_key()).await {
+            Ok(token) => token,
+            Err(e) => {
+                warn!("frontegg token store failed to load token: {e}");
+                None
+            }
+        }
+    }
+
+    async fn store_token(&self, token: &StoredToken) {
+        if let Some(token_store) = &self.token_store {
+            if let Err(e) = token_store.store(&self.token_key(), token).await {
+                warn!("frontegg token store failed to store token: {e}");
+            }
+        }
+    }
+
+    /// Requests a new token from Frontegg.
+    ///
+    /// Callers must hold the `auth_refresh` lock.
+    async fn authenticate(&self) -> Result<SecretString, Error> {
         #[derive(Debug, Clone, Serialize)]
         #[serde(rename_all = "camelCase")]
         struct AuthenticationRequest<'a> {
@@ -131,37 +565,46 @@ impl Client {
             secret: &'a str,
         }
 
+        // Self-hosted deployments may use OAuth-style field names.
         #[derive(Debug, Clone, Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct AuthenticationResponse {
+            #[serde(alias = "accessToken", alias = "access_token")]
             token: String,
+            #[serde(alias = "expires_in")]
             expires_in: u64,
         }
 
-        let mut auth = self.auth.lock().await;
-        match &*auth {
-            Some(auth) if SystemTime::now() < auth.refresh_at => {
-                return Ok(auth.token.clone());
-            }
-            _ => (),
-        }
-        let req = self.build_request(Method::POST, AUTH_VENDOR_PATH);
+        let req = self.build_request(Method::POST, &self.auth_path);
         let req = req.json(&AuthenticationRequest {
             client_id: &self.client_id,
-            secret: &self.secret_key,
+            secret: self.secret_key.expose(),
         });
         let res: AuthenticationResponse = self.send_unauthenticated_request(req).await?;
-        *auth = Some(Auth {
-            token: res.token.clone(),
-            // Refresh twice as frequently as we need to, to be safe.
-            refresh_at: SystemTime::now() + (Duration::from_secs(res.expires_in) / 2),
+        let now = OffsetDateTime::now_utc();
+        let expires_in = Duration::from_secs(res.expires_in);
+        // Refresh twice as frequently as we need to, to be safe.
+        let refresh_at = now + expires_in / 2;
+        let expires_at = now + expires_in;
+        let token = SecretString::new(res.token);
+        self.store_token(&StoredToken {
+            token: token.clone(),
+            refresh_at,
+            expires_at,
+        })
+        .await;
+        *self.auth.lock().expect("lock poisoned") = Some(Auth {
+            token: token.clone(),
+            refresh_at: refresh_at.into(),
+            expires_at: expires_at.into(),
         });
-        Ok(res.token)
+        Ok(token)
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Auth {
-    token: String,
+    token: SecretString,
     refresh_at: SystemTime,
+    expires_at: SystemTime,
 }
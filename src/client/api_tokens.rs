@@ -0,0 +1,120 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::id::TenantId;
+use crate::secret::SecretString;
+use crate::serde::Empty;
+use crate::util::{RequestBuilderExt, StrIteratorExt};
+use crate::{Client, Error};
+
+const TENANT_API_TOKEN_PATH: [&str; 5] = ["identity", "resources", "tenants", "api-tokens", "v1"];
+
+/// The subset of [`ApiToken`] used in create requests.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenRequest<'a> {
+    /// A description of the token.
+    pub description: &'a str,
+    /// The IDs of the roles to grant to the token.
+    pub role_ids: Vec<Uuid>,
+    /// Arbitrary metadata to attach to the token.
+    pub metadata: serde_json::Value,
+    /// The number of minutes after which the token expires.
+    ///
+    /// If unset, the token does not expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in_minutes: Option<u64>,
+}
+
+/// A machine-to-machine API token for a tenant.
+///
+/// The token authenticates via the client credentials flow, using its
+/// [`id`](ApiToken::id) as the client ID and its [`secret`](ApiToken::secret)
+/// as the secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    /// The ID of the token, which serves as its client ID.
+    #[serde(rename = "clientId")]
+    pub id: Uuid,
+    /// The secret for the token.
+    ///
+    /// Only returned when the token is created. Frontegg does not store the
+    /// secret in a retrievable form, so it cannot be recovered later.
+    #[serde(default)]
+    pub secret: Option<SecretString>,
+    /// A description of the token.
+    pub description: Option<String>,
+    /// The IDs of the roles granted to the token.
+    #[serde(default)]
+    pub role_ids: Vec<Uuid>,
+    /// Arbitrary metadata that is attached to the token.
+    #[serde(default = "crate::serde::empty_json_object")]
+    #[serde(with = "crate::serde::nested_json")]
+    pub metadata: serde_json::Value,
+    /// The time at which the token was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Client {
+    /// Creates an API token for a tenant.
+    ///
+    /// The returned token includes its secret, which is not returned by any
+    /// other method. Like other mutating requests, the request is not
+    /// retried, so that a transient failure cannot create duplicate tokens.
+    pub async fn create_tenant_api_token<I>(
+        &self,
+        tenant_id: I,
+        token: &ApiTokenRequest<'_>,
+    ) -> Result<ApiToken, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let req = self.build_request(Method::POST, TENANT_API_TOKEN_PATH);
+        let req = req.tenant(tenant_id).json(token);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Lists the API tokens for a tenant.
+    ///
+    /// The secrets of the returned tokens are absent.
+    pub async fn list_tenant_api_tokens<I>(&self, tenant_id: I) -> Result<Vec<ApiToken>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let req = self.build_request(Method::GET, TENANT_API_TOKEN_PATH);
+        let req = req.tenant(tenant_id);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Deletes an API token for a tenant.
+    pub async fn delete_tenant_api_token<I>(&self, tenant_id: I, id: Uuid) -> Result<(), Error>
+    where
+        I: Into<TenantId>,
+    {
+        let req = self.build_request(Method::DELETE, TENANT_API_TOKEN_PATH.chain_one(id));
+        let req = req.tenant(tenant_id);
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::util::StrIteratorExt;
+use crate::{Client, Error};
+
+const APPLICATION_PATH: [&str; 4] = ["applications", "resources", "applications", "v1"];
+
+/// The subset of [`Application`] used in create requests.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationRequest<'a> {
+    /// The name of the application.
+    pub name: &'a str,
+    /// The URL at which the application is served.
+    #[serde(rename = "appURL")]
+    pub app_url: &'a str,
+    /// The URL of the application's login page.
+    #[serde(rename = "loginURL")]
+    pub login_url: &'a str,
+    /// A description of the application.
+    pub description: Option<&'a str>,
+}
+
+/// A Frontegg application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Application {
+    /// The ID of the application.
+    pub id: Uuid,
+    /// The name of the application.
+    pub name: String,
+    /// The URL at which the application is served.
+    #[serde(rename = "appURL")]
+    pub app_url: Option<String>,
+    /// The URL of the application's login page.
+    #[serde(rename = "loginURL")]
+    pub login_url: Option<String>,
+    /// A description of the application.
+    pub description: Option<String>,
+    /// Whether this is the default application for the workspace.
+    #[serde(default)]
+    pub is_default: bool,
+    /// Whether the application is active.
+    #[serde(default)]
+    pub is_active: bool,
+    /// The time at which the application was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Client {
+    /// Lists all applications.
+    pub async fn list_applications(&self) -> Result<Vec<Application>, Error> {
+        let req = self.build_request(Method::GET, APPLICATION_PATH);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Gets an application by ID.
+    pub async fn get_application(&self, id: Uuid) -> Result<Application, Error> {
+        let req = self.build_request(Method::GET, APPLICATION_PATH.chain_one(id));
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Creates a new application.
+    pub async fn create_application(
+        &self,
+        application: &ApplicationRequest<'_>,
+    ) -> Result<Application, Error> {
+        let req = self.build_request(Method::POST, APPLICATION_PATH);
+        let req = req.json(application);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+}
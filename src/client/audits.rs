@@ -0,0 +1,129 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_core::Stream;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::id::TenantId;
+use crate::util::RequestBuilderExt;
+use crate::{Client, Error};
+
+const AUDIT_LOG_PATH: [&str; 4] = ["audits", "resources", "logs", "v1"];
+
+/// Configuration for the [`Client::list_audit_logs`] operation.
+#[derive(Debug, Clone)]
+pub struct AuditLogConfig {
+    tenant_id: Option<TenantId>,
+    start_time: Option<OffsetDateTime>,
+    end_time: Option<OffsetDateTime>,
+    page_size: u64,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> AuditLogConfig {
+        AuditLogConfig {
+            tenant_id: None,
+            start_time: None,
+            end_time: None,
+            page_size: 50,
+        }
+    }
+}
+
+impl AuditLogConfig {
+    /// Sets the tenant ID to filter audit logs to.
+    ///
+    /// If this method is not called, audit logs for all tenants are returned.
+    pub fn tenant_id<T>(mut self, tenant_id: T) -> Self
+    where
+        T: Into<TenantId>,
+    {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Filters audit logs to entries created at or after the specified time.
+    pub fn start_time(mut self, start_time: OffsetDateTime) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    /// Filters audit logs to entries created before the specified time.
+    pub fn end_time(mut self, end_time: OffsetDateTime) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    /// Sets the page size.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+}
+
+/// An entry in the Frontegg audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// The time at which the audited action occurred.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The ID of the tenant in which the action occurred.
+    pub tenant_id: Option<Uuid>,
+    /// The user who performed the action.
+    pub user: Option<String>,
+    /// The action that was performed.
+    pub action: String,
+    /// The IP address from which the action was performed.
+    pub ip: Option<String>,
+    /// The severity of the entry.
+    pub severity: Option<String>,
+    /// Additional fields attached to the entry.
+    #[serde(flatten)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Client {
+    /// Lists audit log entries, either for all tenants or for a single
+    /// tenant.
+    ///
+    /// The underlying API call is paginated. The returned stream will fetch
+    /// additional pages as it is consumed.
+    pub fn list_audit_logs(
+        &self,
+        config: AuditLogConfig,
+    ) -> impl Stream<Item = Result<AuditLogEntry, Error>> + Send + '_ {
+        self.send_paginated_request(config.page_size, move || {
+            let mut req = self.build_request(Method::GET, AUDIT_LOG_PATH);
+            if let Some(tenant_id) = config.tenant_id {
+                req = req.tenant(tenant_id);
+            }
+            for (key, time) in [
+                ("startDate", config.start_time),
+                ("endDate", config.end_time),
+            ] {
+                if let Some(time) = time {
+                    let time = time.format(&Rfc3339).expect("valid timestamp");
+                    req = req.query(&[(key, time)]);
+                }
+            }
+            req
+        })
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::id::TenantId;
+use crate::serde::Empty;
+use crate::util::{RequestBuilderExt, StrIteratorExt};
+use crate::{Client, Error};
+
+const TENANT_DOMAIN_PATH: [&str; 4] = ["tenants", "resources", "domains", "v1"];
+
+/// An email domain associated with a Frontegg tenant.
+///
+/// Users with an email address in a tenant's domains may join the tenant
+/// automatically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantDomain {
+    /// The ID of the domain.
+    pub id: Uuid,
+    /// The domain name, e.g., `example.com`.
+    pub domain: String,
+    /// Whether ownership of the domain has been verified.
+    #[serde(default)]
+    pub validated: bool,
+    /// The time at which the domain was added to the tenant.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Client {
+    /// Lists the email domains associated with a tenant.
+    pub async fn get_tenant_domains<I>(&self, tenant_id: I) -> Result<Vec<TenantDomain>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::GET, TENANT_DOMAIN_PATH);
+        let req = req.tenant(tenant_id);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Associates an email domain with a tenant.
+    ///
+    /// Fails with a `409 Conflict` error if the domain is already associated
+    /// with the tenant.
+    pub async fn add_tenant_domain<I>(
+        &self,
+        tenant_id: I,
+        domain: &str,
+    ) -> Result<TenantDomain, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::POST, TENANT_DOMAIN_PATH);
+        let req = req.tenant(tenant_id);
+        let req = req.json(&json!({ "domain": domain }));
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Removes an email domain from a tenant by the domain's ID.
+    pub async fn remove_tenant_domain<I>(&self, tenant_id: I, domain_id: Uuid) -> Result<(), Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::DELETE, TENANT_DOMAIN_PATH.chain_one(domain_id));
+        let req = req.tenant(tenant_id);
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+}
@@ -13,11 +13,92 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use futures_core::Stream;
+use futures_util::{pin_mut, TryStreamExt};
+use reqwest::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::error::ApiError;
+use crate::id::TenantId;
+use crate::util::{RequestBuilderExt, StrIteratorExt};
+use crate::{Client, Error};
+
+const ROLE_PATH: [&str; 4] = ["identity", "resources", "roles", "v2"];
+const PERMISSION_PATH: [&str; 4] = ["identity", "resources", "permissions", "v2"];
+const PERMISSION_PATH_V1: [&str; 4] = ["identity", "resources", "permissions", "v1"];
+
+/// Configuration for the [`Client::stream_roles`] operation.
+#[derive(Debug, Clone)]
+pub struct RoleListConfig {
+    tenant_id: Option<TenantId>,
+    page_size: u64,
+}
+
+impl Default for RoleListConfig {
+    fn default() -> RoleListConfig {
+        RoleListConfig {
+            tenant_id: None,
+            page_size: 50,
+        }
+    }
+}
+
+impl RoleListConfig {
+    /// Sets the tenant whose roles to list.
+    ///
+    /// Roles defined specifically for the tenant are returned in addition to
+    /// the roles defined for the workspace. If this method is not called,
+    /// only the roles defined for the workspace are returned.
+    pub fn tenant_id<T>(mut self, tenant_id: T) -> Self
+    where
+        T: Into<TenantId>,
+    {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Sets the page size.
+    ///
+    /// The default page size is 50.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+}
+
+/// Configuration for the [`Client::stream_permissions`] operation.
+#[derive(Debug, Clone)]
+pub struct PermissionListConfig {
+    page_size: u64,
+}
+
+impl Default for PermissionListConfig {
+    fn default() -> PermissionListConfig {
+        PermissionListConfig { page_size: 50 }
+    }
+}
+
+impl PermissionListConfig {
+    /// Sets the page size.
+    ///
+    /// The default page size is 50.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+}
+
 /// A Frontegg role.
+///
+/// Roles are compared, ordered, and hashed by ID alone, so that sets of roles
+/// can be diffed by identity. Two roles with the same ID are equal even if
+/// their other fields differ.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Role {
@@ -30,18 +111,26 @@ pub struct Role {
     /// A description of the role.
     pub description: Option<String>,
     /// The level of the role.
+    #[serde(deserialize_with = "crate::serde::lenient::deserialize")]
     pub level: i64,
     /// Whether the role is a default role assigned to new users.
+    #[serde(deserialize_with = "crate::serde::lenient::deserialize")]
     pub is_default: bool,
     /// The IDs of the permissions granted by the role.
     #[serde(rename = "permissions")]
     pub permission_ids: Vec<Uuid>,
     /// The time at which the role was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
 }
 
 /// A Frontegg permission.
+///
+/// Like [`Role`]s, permissions are compared, ordered, and hashed by ID alone.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Permission {
@@ -56,9 +145,182 @@ pub struct Permission {
     /// A description of the permission.
     pub description: Option<String>,
     /// The time at which the permission was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
     /// The time at which the permission was updated.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub updated_at: OffsetDateTime,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl PartialEq for Role {
+    fn eq(&self, other: &Role) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Role {}
+
+impl PartialOrd for Role {
+    fn partial_cmp(&self, other: &Role) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Role {
+    fn cmp(&self, other: &Role) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for Role {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl PartialEq for Permission {
+    fn eq(&self, other: &Permission) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Permission {}
+
+impl PartialOrd for Permission {
+    fn partial_cmp(&self, other: &Permission) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Permission {
+    fn cmp(&self, other: &Permission) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for Permission {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+/// A category of Frontegg permissions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCategory {
+    /// The ID of the category.
+    pub id: String,
+    /// The human-readable name for the category.
+    pub name: String,
+    /// A description of the category.
+    pub description: Option<String>,
+}
+
+impl Client {
+    /// Lists all roles in the workspace.
+    ///
+    /// All pages are fetched before returning. To process roles as they are
+    /// fetched, use [`Client::stream_roles`].
+    pub async fn list_roles(&self) -> Result<Vec<Role>, Error> {
+        self.stream_roles(RoleListConfig::default())
+            .try_collect()
+            .await
+    }
+
+    /// Lists roles in the workspace.
+    ///
+    /// The underlying API call is paginated. The returned stream will fetch
+    /// additional pages as it is consumed.
+    pub fn stream_roles(
+        &self,
+        config: RoleListConfig,
+    ) -> impl Stream<Item = Result<Role, Error>> + Send + '_ {
+        self.send_paginated_request(config.page_size, move || {
+            let req = self.build_request(Method::GET, ROLE_PATH);
+            match config.tenant_id {
+                Some(tenant_id) => req.tenant(tenant_id),
+                None => req,
+            }
+        })
+    }
+
+    /// Lists all roles available to a tenant, including the roles defined
+    /// specifically for the tenant.
+    ///
+    /// All pages are fetched before returning. See
+    /// [`RoleListConfig::tenant_id`] to stream a tenant's roles instead.
+    pub async fn list_tenant_roles<I>(&self, tenant_id: I) -> Result<Vec<Role>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        self.stream_roles(RoleListConfig::default().tenant_id(tenant_id))
+            .try_collect()
+            .await
+    }
+
+    /// Lists all permissions in the workspace.
+    ///
+    /// All pages are fetched before returning. To process permissions as they
+    /// are fetched, use [`Client::stream_permissions`].
+    pub async fn list_permissions(&self) -> Result<Vec<Permission>, Error> {
+        self.stream_permissions(PermissionListConfig::default())
+            .try_collect()
+            .await
+    }
+
+    /// Gets a permission by ID.
+    pub async fn get_permission(&self, id: Uuid) -> Result<Permission, Error> {
+        let req = self.build_request(Method::GET, PERMISSION_PATH_V1.chain_one(id));
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Gets a permission by its machine-readable key, e.g., `fga.write`.
+    ///
+    /// Unlike IDs, keys are consistent across workspaces. Frontegg does not
+    /// support filtering permissions by key, so permissions are listed until
+    /// the matching permission is found.
+    ///
+    /// Returns an error with a `404 Not Found` status code if no permission
+    /// has the specified key.
+    pub async fn get_permission_by_key(&self, key: &str) -> Result<Permission, Error> {
+        let permissions = self.stream_permissions(PermissionListConfig::default());
+        pin_mut!(permissions);
+        while let Some(permission) = permissions.try_next().await? {
+            if permission.key == key {
+                return Ok(permission);
+            }
+        }
+        Err(Error::Api(ApiError::new(
+            StatusCode::NOT_FOUND,
+            vec![format!("permission with key {key:?} not found")],
+        )))
+    }
+
+    /// Lists all permission categories in the workspace.
+    ///
+    /// The category of a permission is identified by
+    /// [`Permission::category_id`].
+    pub async fn list_permission_categories(&self) -> Result<Vec<PermissionCategory>, Error> {
+        let req = self.build_request(Method::GET, PERMISSION_PATH_V1.chain_one("categories"));
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Lists permissions in the workspace.
+    ///
+    /// The underlying API call is paginated. The returned stream will fetch
+    /// additional pages as it is consumed.
+    pub fn stream_permissions(
+        &self,
+        config: PermissionListConfig,
+    ) -> impl Stream<Item = Result<Permission, Error>> + Send + '_ {
+        self.send_paginated_request(config.page_size, || {
+            self.build_request(Method::GET, PERMISSION_PATH)
+        })
+    }
 }
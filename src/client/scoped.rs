@@ -0,0 +1,89 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_core::Stream;
+use uuid::Uuid;
+
+use crate::id::{TenantId, UserId};
+use crate::{Client, CreatedUser, Error, User, UserListConfig, UserRequest};
+
+/// A view of a [`Client`] that is scoped to a single tenant.
+///
+/// Returned by [`Client::scoped_to_tenant`]. Each method is equivalent to the
+/// [`Client`] method of the same name, but operates within the scoped tenant
+/// rather than requiring the tenant to be specified with every call.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantScopedClient<'a> {
+    client: &'a Client,
+    tenant_id: TenantId,
+}
+
+impl Client {
+    /// Returns a view of this client that is scoped to the specified tenant.
+    ///
+    /// The view borrows this client and is cheap to construct.
+    pub fn scoped_to_tenant<T>(&self, tenant_id: T) -> TenantScopedClient<'_>
+    where
+        T: Into<TenantId>,
+    {
+        TenantScopedClient {
+            client: self,
+            tenant_id: tenant_id.into(),
+        }
+    }
+}
+
+impl<'a> TenantScopedClient<'a> {
+    /// Returns the ID of the tenant to which this client is scoped.
+    pub fn tenant_id(&self) -> TenantId {
+        self.tenant_id
+    }
+
+    /// Creates a new user in the scoped tenant.
+    ///
+    /// The `tenant_id` of the request is ignored. See [`Client::create_user`]
+    /// for details.
+    pub async fn create_user(&self, user: &UserRequest<'_>) -> Result<CreatedUser, Error> {
+        self.client
+            .create_user(&UserRequest {
+                tenant_id: self.tenant_id.into(),
+                ..user.clone()
+            })
+            .await
+    }
+
+    /// Lists the users in the scoped tenant.
+    ///
+    /// Any tenants specified in `config` are replaced with the scoped tenant.
+    /// See [`Client::list_users`] for details.
+    pub fn list_users(
+        &self,
+        config: UserListConfig,
+    ) -> impl Stream<Item = Result<User, Error>> + Send + 'a {
+        self.client.list_users(config.tenant_id(self.tenant_id))
+    }
+
+    /// Assigns roles to a user in the scoped tenant.
+    ///
+    /// See [`Client::assign_user_roles`] for details.
+    pub async fn assign_user_roles<U>(&self, user_id: U, role_ids: &[Uuid]) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+    {
+        self.client
+            .assign_user_roles(user_id, self.tenant_id, role_ids)
+            .await
+    }
+}
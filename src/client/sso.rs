@@ -0,0 +1,140 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::id::TenantId;
+use crate::util::{RequestBuilderExt, StrIteratorExt};
+use crate::{Client, Error};
+
+const SSO_CONFIGURATION_PATH: [&str; 5] = ["team", "resources", "sso", "v1", "configurations"];
+
+/// The subset of [`SsoConfig`] used in set requests.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoConfigRequest<'a> {
+    /// Whether SSO is enabled for the tenant.
+    pub enabled: bool,
+    /// The email domain whose users authenticate via SSO.
+    pub domain: &'a str,
+    /// The URL of the identity provider's sign-on endpoint.
+    pub sso_endpoint: Option<&'a str>,
+    /// The identity provider's public certificate, in PEM format.
+    pub public_certificate: Option<&'a str>,
+    /// Whether to sign SAML requests sent to the identity provider.
+    pub sign_request: bool,
+}
+
+/// The SSO configuration for a Frontegg tenant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoConfig {
+    /// The ID of the SSO configuration.
+    pub id: Uuid,
+    /// Whether SSO is enabled for the tenant.
+    pub enabled: bool,
+    /// The email domain whose users authenticate via SSO.
+    pub domain: String,
+    /// Whether ownership of the domain has been verified.
+    #[serde(default, deserialize_with = "crate::serde::lenient::deserialize")]
+    pub validated: bool,
+    /// The assertion consumer service (ACS) URL to configure in the identity
+    /// provider.
+    pub acs_url: Option<String>,
+    /// The service provider entity ID to configure in the identity provider.
+    pub sp_entity_id: Option<String>,
+    /// The URL of the identity provider's sign-on endpoint.
+    pub sso_endpoint: Option<String>,
+    /// The identity provider's public certificate, in PEM format.
+    pub public_certificate: Option<String>,
+    /// Whether SAML requests sent to the identity provider are signed.
+    #[serde(default, deserialize_with = "crate::serde::lenient::deserialize")]
+    pub sign_request: bool,
+    /// The time at which the SSO configuration was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The time at which the SSO configuration was updated.
+    #[serde(with = "crate::serde::timestamp")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl Client {
+    /// Lists the SSO configurations for a tenant.
+    ///
+    /// Frontegg permits a tenant to have several SSO configurations, though
+    /// [`Client::set_tenant_sso_config`] maintains only one.
+    pub async fn list_tenant_sso_configs<I>(&self, tenant_id: I) -> Result<Vec<SsoConfig>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::GET, SSO_CONFIGURATION_PATH);
+        let req = req.tenant(tenant_id);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+
+    /// Gets the SSO configuration for a tenant.
+    ///
+    /// If the tenant has several SSO configurations, returns the one that was
+    /// created first, which is the one that [`Client::set_tenant_sso_config`]
+    /// updates. Use [`Client::list_tenant_sso_configs`] to get them all.
+    ///
+    /// Returns an error with a `404 Not Found` status code if the tenant has
+    /// no SSO configuration.
+    pub async fn get_tenant_sso_config<I>(&self, tenant_id: I) -> Result<SsoConfig, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let configs = self.list_tenant_sso_configs(tenant_id).await?;
+        match configs.into_iter().min_by_key(|c| c.created_at) {
+            Some(config) => Ok(config),
+            None => Err(Error::Api(ApiError::new(
+                StatusCode::NOT_FOUND,
+                vec!["SSO configuration not found".into()],
+            ))),
+        }
+    }
+
+    /// Sets the SSO configuration for a tenant.
+    ///
+    /// Updates the configuration returned by [`Client::get_tenant_sso_config`]
+    /// if the tenant has one, and creates a configuration otherwise.
+    pub async fn set_tenant_sso_config<I>(
+        &self,
+        tenant_id: I,
+        config: &SsoConfigRequest<'_>,
+    ) -> Result<SsoConfig, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let configs = self.list_tenant_sso_configs(tenant_id).await?;
+        let req = match configs.into_iter().min_by_key(|c| c.created_at) {
+            Some(existing) => {
+                self.build_request(Method::PATCH, SSO_CONFIGURATION_PATH.chain_one(existing.id))
+            }
+            None => self.build_request(Method::POST, SSO_CONFIGURATION_PATH),
+        };
+        let req = req.tenant(tenant_id);
+        let req = req.json(config);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+}
@@ -13,20 +13,194 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use futures_core::Stream;
+use futures_util::future;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
 use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::serde::Empty;
-use crate::util::StrIteratorExt;
-use crate::{error, Client, Error};
+use crate::client::{AsyncOperation, SortOrder};
+use crate::error::ApiError;
+use crate::id::TenantId;
+use crate::util::{self, StrIteratorExt};
+use crate::{Client, Error, User, UserListConfig};
 
 const TENANT_PATH: [&str; 4] = ["tenants", "resources", "tenants", "v1"];
+const TENANT_PATH_V2: [&str; 4] = ["tenants", "resources", "tenants", "v2"];
+
+/// The field by which to sort tenants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantSortBy {
+    /// Sort by tenant ID.
+    Id,
+    /// Sort by tenant name.
+    Name,
+    /// Sort by creation time.
+    CreatedAt,
+}
+
+impl TenantSortBy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TenantSortBy::Id => "tenantId",
+            TenantSortBy::Name => "name",
+            TenantSortBy::CreatedAt => "createdAt",
+        }
+    }
+}
+
+/// The lifecycle status of a tenant.
+///
+/// Unrecognized statuses are preserved in the [`TenantStatus::Other`] variant.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum TenantStatus {
+    /// The tenant is active.
+    Active,
+    /// The tenant is suspended.
+    Suspended,
+    /// The tenant is deleted.
+    Deleted,
+    /// A status not known to this library.
+    Other(String),
+}
+
+impl From<String> for TenantStatus {
+    fn from(s: String) -> TenantStatus {
+        match s.to_ascii_lowercase().as_str() {
+            "active" => TenantStatus::Active,
+            "suspended" => TenantStatus::Suspended,
+            "deleted" => TenantStatus::Deleted,
+            _ => TenantStatus::Other(s),
+        }
+    }
+}
+
+impl From<TenantStatus> for String {
+    fn from(status: TenantStatus) -> String {
+        match status {
+            TenantStatus::Active => "active".into(),
+            TenantStatus::Suspended => "suspended".into(),
+            TenantStatus::Deleted => "deleted".into(),
+            TenantStatus::Other(s) => s,
+        }
+    }
+}
+
+/// Configuration for the [`Client::stream_tenants`] operation.
+#[derive(Debug, Clone)]
+pub struct TenantListConfig {
+    name_filter: Option<String>,
+    sort_by: Option<TenantSortBy>,
+    order: Option<SortOrder>,
+    include_deleted: bool,
+    only_deleted: bool,
+    extra_query_params: Vec<(String, String)>,
+    page_size: u64,
+}
+
+impl Default for TenantListConfig {
+    fn default() -> TenantListConfig {
+        TenantListConfig {
+            name_filter: None,
+            sort_by: None,
+            order: None,
+            include_deleted: false,
+            only_deleted: false,
+            extra_query_params: vec![],
+            page_size: 50,
+        }
+    }
+}
+
+impl TenantListConfig {
+    /// Filters tenants to those whose name matches the specified filter.
+    ///
+    /// Filtering is performed by the Frontegg API.
+    pub fn name_filter<S>(mut self, filter: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.name_filter = Some(filter.into());
+        self
+    }
+
+    /// Sets the field by which to sort tenants.
+    ///
+    /// If this method is not called, the order of the returned tenants is
+    /// determined by the Frontegg API.
+    pub fn sort_by(mut self, sort_by: TenantSortBy) -> Self {
+        self.sort_by = Some(sort_by);
+        self
+    }
+
+    /// Sets the order in which to sort tenants.
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Sets whether to include tenants that have been deleted, i.e., whose
+    /// [`Tenant::deleted_at`] is set.
+    ///
+    /// Frontegg does not support filtering tenants by deletion status, so
+    /// filtering is performed by the client. Defaults to `false`.
+    pub fn include_deleted(mut self, include_deleted: bool) -> Self {
+        self.include_deleted = include_deleted;
+        self
+    }
+
+    /// Sets whether to include only tenants that have been deleted.
+    ///
+    /// Takes precedence over [`TenantListConfig::include_deleted`]. Like that
+    /// method, filtering is performed by the client. Defaults to `false`.
+    pub fn only_deleted(mut self, only_deleted: bool) -> Self {
+        self.only_deleted = only_deleted;
+        self
+    }
+
+    /// Reports whether the deletion status of `tenant` passes this
+    /// configuration's filters.
+    fn matches_deleted(&self, tenant: &Tenant) -> bool {
+        match tenant.deleted_at {
+            Some(_) => self.include_deleted || self.only_deleted,
+            None => !self.only_deleted,
+        }
+    }
+
+    /// Sets the page size.
+    ///
+    /// The default page size is 50.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Adds a query parameter to send with each request, in addition to
+    /// those configured by other methods.
+    ///
+    /// This is an escape hatch for query parameters that Frontegg supports
+    /// but this crate does not yet expose. The parameter is passed through
+    /// without validation, and may be specified multiple times.
+    pub fn extra_query_param<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.extra_query_params.push((key.into(), value.into()));
+        self
+    }
+}
 
 /// The subset of [`Tenant`] used in create requests.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TenantRequest<'a> {
     /// The ID of the tenant.
@@ -35,6 +209,12 @@ pub struct TenantRequest<'a> {
     /// The name of the tenant.
     pub name: &'a str,
     /// Arbitrary metadata to attach to the tenant.
+    ///
+    /// Frontegg stores any JSON value, including non-object values like
+    /// numbers, and returns it unchanged in [`Tenant::metadata`]. `null`
+    /// attaches no metadata. However, [`Client::set_tenant_metadata`] and
+    /// [`Client::delete_tenant_metadata`] operate on individual keys, so use
+    /// an object if the metadata will later be updated.
     pub metadata: serde_json::Value,
     /// The name of the person who created the tenant.
     pub creator_name: Option<&'a str>,
@@ -42,8 +222,63 @@ pub struct TenantRequest<'a> {
     pub creator_email: Option<&'a str>,
 }
 
+impl<'a> TenantRequest<'a> {
+    /// Returns a builder for a request to create a tenant with the specified
+    /// ID and name.
+    ///
+    /// The required fields are passed up front, so a request built via the
+    /// builder cannot omit them.
+    pub fn builder<I>(id: I, name: &'a str) -> TenantRequestBuilder<'a>
+    where
+        I: Into<TenantId>,
+    {
+        TenantRequestBuilder {
+            req: TenantRequest {
+                id: id.into().as_uuid(),
+                name,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// A builder for a [`TenantRequest`].
+///
+/// Created via [`TenantRequest::builder`].
+#[derive(Debug, Clone)]
+pub struct TenantRequestBuilder<'a> {
+    req: TenantRequest<'a>,
+}
+
+impl<'a> TenantRequestBuilder<'a> {
+    /// Sets the metadata to attach to the tenant.
+    ///
+    /// See [`TenantRequest::metadata`] for details.
+    pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.req.metadata = metadata;
+        self
+    }
+
+    /// Sets the name of the person who created the tenant.
+    pub fn creator_name(mut self, creator_name: &'a str) -> Self {
+        self.req.creator_name = Some(creator_name);
+        self
+    }
+
+    /// Sets the email of the person who created the tenant.
+    pub fn creator_email(mut self, creator_email: &'a str) -> Self {
+        self.req.creator_email = Some(creator_email);
+        self
+    }
+
+    /// Builds the request.
+    pub fn build(self) -> TenantRequest<'a> {
+        self.req
+    }
+}
+
 /// A Frontegg tenant.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tenant {
     /// The ID of the tenant.
@@ -53,33 +288,150 @@ pub struct Tenant {
     pub name: String,
     /// Arbitrary metadata that is attached to the tenant.
     #[serde(default = "crate::serde::empty_json_object")]
-    #[serde(deserialize_with = "crate::serde::nested_json::deserialize")]
+    #[serde(with = "crate::serde::nested_json")]
     pub metadata: serde_json::Value,
     /// The name of the person who created the tenant.
     pub creator_name: Option<String>,
     /// The email of the person who created the tenant.
     pub creator_email: Option<String>,
     /// The time at which the tenant was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
     /// The time at which the tenant was updated.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub updated_at: OffsetDateTime,
     /// The time at which the tenant was deleted.
-    #[serde(with = "time::serde::rfc3339::option")]
+    #[serde(with = "crate::serde::timestamp::option")]
     pub deleted_at: Option<OffsetDateTime>,
+    /// The lifecycle status of the tenant, if reported.
+    #[serde(default)]
+    pub status: Option<TenantStatus>,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented, like subscription or plan information.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Tenant {
+    /// Deserializes the tenant's metadata into the specified type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// # fn example(tenant: frontegg::Tenant) -> Result<(), serde_json::Error> {
+    /// #[derive(Deserialize)]
+    /// struct TenantMetadata {
+    ///     plan: String,
+    /// }
+    ///
+    /// let metadata: TenantMetadata = tenant.metadata_as()?;
+    /// println!("tenant is on the {} plan", metadata.plan);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metadata_as<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        T::deserialize(&self.metadata)
+    }
+
+    /// Deserializes a single top-level key of the tenant's metadata into the
+    /// specified type.
+    ///
+    /// Returns `None` if the key is missing or its value cannot be
+    /// deserialized into `T`. Use [`Tenant::try_metadata_get`] to distinguish
+    /// the two cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn example(tenant: frontegg::Tenant) {
+    /// if tenant.metadata_get::<bool>("feature_x").unwrap_or(false) {
+    ///     println!("feature x is enabled");
+    /// }
+    /// # }
+    /// ```
+    pub fn metadata_get<T>(&self, key: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.try_metadata_get(key).ok().flatten()
+    }
+
+    /// Like [`Tenant::metadata_get`], but returns an error rather than `None`
+    /// if the key's value cannot be deserialized into `T`.
+    pub fn try_metadata_get<T>(&self, key: &str) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.metadata.get(key).map(T::deserialize).transpose()
+    }
+
+    /// Returns [`Tenant::created_at`] as a [`chrono::DateTime`].
+    pub fn created_at_chrono(&self) -> chrono::DateTime<Utc> {
+        util::to_chrono(self.created_at)
+    }
+
+    /// Returns [`Tenant::updated_at`] as a [`chrono::DateTime`].
+    pub fn updated_at_chrono(&self) -> chrono::DateTime<Utc> {
+        util::to_chrono(self.updated_at)
+    }
+
+    /// Returns [`Tenant::deleted_at`] as a [`chrono::DateTime`].
+    pub fn deleted_at_chrono(&self) -> Option<chrono::DateTime<Utc>> {
+        self.deleted_at.map(util::to_chrono)
+    }
 }
 
 impl Client {
     /// Lists all tenants in the workspace.
     ///
-    /// The returned vector is sorted by tenant ID.
+    /// The returned vector is sorted by tenant ID and contains each tenant
+    /// at most once. Tenants that have been deleted are excluded; to include
+    /// them, use [`Client::stream_tenants`] with
+    /// [`TenantListConfig::include_deleted`].
     pub async fn list_tenants(&self) -> Result<Vec<Tenant>, Error> {
         let req = self.build_request(Method::GET, TENANT_PATH);
-        let res = self.send_request(req).await?;
+        let mut res: Vec<Tenant> = self.send_request(req).await?;
+        res.retain(|tenant| tenant.deleted_at.is_none());
+        // Frontegg does not guarantee an order, so sort the tenants here to
+        // uphold the documented ordering.
+        res.sort_by_key(|tenant| tenant.id);
+        res.dedup_by_key(|tenant| tenant.id);
         Ok(res)
     }
 
+    /// Lists tenants in the workspace, with optional filtering and sorting.
+    ///
+    /// The underlying API call is paginated. The returned stream will fetch
+    /// additional pages as it is consumed.
+    ///
+    /// Unless [`TenantListConfig::include_deleted`] or
+    /// [`TenantListConfig::only_deleted`] is specified, tenants that have been
+    /// deleted are excluded.
+    pub fn stream_tenants(
+        &self,
+        config: TenantListConfig,
+    ) -> impl Stream<Item = Result<Tenant, Error>> + Send + '_ {
+        let filter = config.clone();
+        self.send_paginated_request(config.page_size, move || {
+            let mut req = self.build_request(Method::GET, TENANT_PATH_V2);
+            if let Some(name_filter) = &config.name_filter {
+                req = req.query(&[("_filter", name_filter)]);
+            }
+            if let Some(sort_by) = config.sort_by {
+                req = req.query(&[("_sortBy", sort_by.as_str())]);
+            }
+            if let Some(order) = config.order {
+                req = req.query(&[("_order", order.as_str())]);
+            }
+            req.query(&config.extra_query_params)
+        })
+        .try_filter(move |tenant| future::ready(filter.matches_deleted(tenant)))
+    }
+
     /// Creates a new tenant.
     pub async fn create_tenant(&self, tenant: &TenantRequest<'_>) -> Result<Tenant, Error> {
         let req = self.build_request(Method::POST, TENANT_PATH);
@@ -88,31 +440,222 @@ impl Client {
         Ok(res)
     }
 
+    /// Creates multiple tenants.
+    ///
+    /// The tenants are created concurrently, with at most the number of
+    /// requests configured via [`ClientBuilder::with_bulk_concurrency`] in
+    /// flight at once. The result of each creation is returned in the same
+    /// order as `tenants`. The failure to create one tenant does not prevent
+    /// the creation of the others.
+    ///
+    /// [`ClientBuilder::with_bulk_concurrency`]: crate::ClientBuilder::with_bulk_concurrency
+    pub async fn create_tenants(
+        &self,
+        tenants: &[TenantRequest<'_>],
+    ) -> Vec<Result<Tenant, Error>> {
+        stream::iter(tenants)
+            .map(|tenant| self.create_tenant(tenant))
+            .buffered(self.bulk_concurrency)
+            .collect()
+            .await
+    }
+
+    /// Creates a tenant, or updates it if a tenant with the same ID exists.
+    ///
+    /// The tenant is first created as if via [`Client::create_tenant`]. If
+    /// that request fails with a `409 Conflict` error, the existing tenant is
+    /// updated to match `tenant` via further requests: its name is replaced,
+    /// and its metadata is updated as follows.
+    ///
+    ///   * Metadata that is `null` leaves the existing metadata unchanged.
+    ///   * The keys of metadata that is an object are merged into the
+    ///     existing metadata, as via [`Client::set_tenant_metadata`].
+    ///   * Metadata of any other type replaces the existing metadata.
+    ///
+    /// The creator fields are only applied if the tenant is newly created.
+    ///
+    /// The requests are not atomic. If the tenant is deleted between the
+    /// requests, the update will fail with a `404 Not Found` error.
+    pub async fn upsert_tenant(&self, tenant: &TenantRequest<'_>) -> Result<Tenant, Error> {
+        match self.create_tenant(tenant).await {
+            Err(Error::Api(e)) if e.status_code == StatusCode::CONFLICT => {
+                let mut body = json!({ "name": tenant.name });
+                if !tenant.metadata.is_null() && !tenant.metadata.is_object() {
+                    body["metadata"] = tenant.metadata.clone();
+                }
+                let req = self.build_request(Method::PUT, TENANT_PATH.chain_one(tenant.id));
+                let req = req.json(&body);
+                let res = self.send_request(req).await?;
+                if tenant.metadata.is_object() {
+                    return self.set_tenant_metadata(tenant.id, &tenant.metadata).await;
+                }
+                Ok(res)
+            }
+            res => res,
+        }
+    }
+
     /// Get a tenant by ID.
-    pub async fn get_tenant(&self, id: Uuid) -> Result<Tenant, Error> {
+    pub async fn get_tenant<I>(&self, id: I) -> Result<Tenant, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
         let req = self.build_request(Method::GET, TENANT_PATH.chain_one(id));
-        let mut res: Vec<Tenant> = self.send_request(req).await?;
-        res.pop().ok_or(Error::Api(error::ApiError {
-            status_code: StatusCode::NOT_FOUND,
-            messages: vec!["Tenant not found".to_string()],
-        }))
+        let res = self.send_request(req).await?;
+        single_tenant(res)
+    }
+
+    /// Gets a tenant by ID, if the tenant exists.
+    ///
+    /// Returns `None` rather than a `404 Not Found` error if the tenant does
+    /// not exist.
+    pub async fn try_get_tenant<I>(&self, id: I) -> Result<Option<Tenant>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        match self.get_tenant(id).await {
+            Ok(tenant) => Ok(Some(tenant)),
+            Err(Error::Api(e)) if e.status_code == StatusCode::NOT_FOUND => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets a tenant by ID, unless the tenant is unchanged.
+    ///
+    /// If `etag` is specified and matches the tenant's current entity tag,
+    /// returns `None`. Otherwise, returns the tenant and its current entity
+    /// tag, if Frontegg reported one. Callers are responsible for storing the
+    /// entity tag to pass to subsequent calls.
+    pub async fn get_tenant_conditional<I>(
+        &self,
+        id: I,
+        etag: Option<&str>,
+    ) -> Result<Option<(Tenant, Option<String>)>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        let req = self.build_request(Method::GET, TENANT_PATH.chain_one(id));
+        match self.send_conditional_request(req, etag).await? {
+            Some((res, etag)) => Ok(Some((single_tenant(res)?, etag))),
+            None => Ok(None),
+        }
     }
 
     /// Deletes a tenant by ID.
-    pub async fn delete_tenant(&self, id: Uuid) -> Result<(), Error> {
-        let req = self.build_request(Method::DELETE, TENANT_PATH.chain_one(id));
-        let _: Empty = self.send_request(req).await?;
+    ///
+    /// The tenant is deleted without checking whether it still has users.
+    /// What becomes of any remaining users is determined by Frontegg. Use
+    /// [`Client::delete_tenant_with_options`] to control the treatment of
+    /// remaining users explicitly.
+    ///
+    /// If Frontegg accepts the deletion for asynchronous processing, this
+    /// method returns once the deletion is accepted. Use
+    /// [`Client::request_tenant_deletion`] to distinguish that case.
+    pub async fn delete_tenant<I>(&self, id: I) -> Result<(), Error>
+    where
+        I: Into<TenantId>,
+    {
+        self.request_tenant_deletion(id).await?;
         Ok(())
     }
 
+    /// Deletes a tenant by ID, reporting whether Frontegg completed the
+    /// deletion.
+    ///
+    /// Returns `None` if the tenant was deleted, or a reference to the
+    /// deletion if Frontegg accepted it for asynchronous processing. In the
+    /// latter case, the tenant may remain visible until the deletion
+    /// completes.
+    pub async fn request_tenant_deletion<I>(&self, id: I) -> Result<Option<AsyncOperation>, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        let req = self.build_request(Method::DELETE, TENANT_PATH.chain_one(id));
+        self.send_request_or_accepted(req).await
+    }
+
+    /// Deletes a tenant by ID, with explicit treatment of the tenant's users.
+    ///
+    /// If `cascade` is `false`, the tenant is deleted only if it has no
+    /// users. Otherwise, the deletion fails with an [`Error::TenantHasUsers`]
+    /// error without sending a delete request. The users are counted in a
+    /// separate request before the tenant is deleted, so a user added to the
+    /// tenant in between does not prevent the deletion.
+    ///
+    /// If `cascade` is `true`, each of the tenant's users is first removed
+    /// from the tenant as if via [`Client::remove_user_from_tenant`], which
+    /// deletes users that belong to no other tenant. The removals are
+    /// performed concurrently, as configured via
+    /// [`ClientBuilder::with_bulk_concurrency`]. If any removal fails, the
+    /// tenant is not deleted and the first error is returned; users removed
+    /// before the failure remain removed.
+    ///
+    /// Neither path is atomic. Users added to the tenant while the deletion is
+    /// in progress are treated as by [`Client::delete_tenant`].
+    ///
+    /// [`ClientBuilder::with_bulk_concurrency`]: crate::ClientBuilder::with_bulk_concurrency
+    pub async fn delete_tenant_with_options<I>(&self, id: I, cascade: bool) -> Result<(), Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        if cascade {
+            let users: Vec<User> = self
+                .list_users(UserListConfig::default().tenant_id(id))
+                .try_collect()
+                .await?;
+            stream::iter(users)
+                .map(|user| self.remove_user_from_tenant(user.id, id))
+                .buffer_unordered(self.bulk_concurrency)
+                .try_collect::<()>()
+                .await?;
+        } else {
+            let users = self.count_tenant_users(id).await?;
+            if users > 0 {
+                return Err(Error::TenantHasUsers {
+                    tenant_id: id,
+                    users,
+                });
+            }
+        }
+        self.delete_tenant(id).await
+    }
+
+    /// Deletes a tenant by ID, if the tenant exists.
+    ///
+    /// Returns `true` if the tenant was deleted or `false` if the tenant did
+    /// not exist.
+    pub async fn delete_tenant_if_exists<I>(&self, id: I) -> Result<bool, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        match self.delete_tenant(id).await {
+            Ok(()) => Ok(true),
+            Err(Error::Api(e)) if e.status_code == StatusCode::NOT_FOUND => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Set tenant metadata with an optional key
     ///
     /// This does not remove existing keys from the object if omitted.
-    pub async fn set_tenant_metadata(
+    ///
+    /// The keys of `metadata` are merged into the tenant's existing metadata,
+    /// so both are expected to be objects.
+    pub async fn set_tenant_metadata<I>(
         &self,
-        id: Uuid,
+        id: I,
         metadata: &serde_json::Value,
-    ) -> Result<Tenant, Error> {
+    ) -> Result<Tenant, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
         let req = self
             .build_request(
                 Method::POST,
@@ -124,7 +667,19 @@ impl Client {
     }
 
     /// Remove a key/value from a tenant's metadata
-    pub async fn delete_tenant_metadata(&self, id: Uuid, key: &str) -> Result<Tenant, Error> {
+    ///
+    /// The key is percent-encoded, so it may contain characters like `/` and
+    /// spaces. The keys `""`, `"."`, and `".."` are rejected with an
+    /// [`Error::InvalidMetadataKey`] error without sending a request: they
+    /// cannot be expressed as a path segment, as `"."` and `".."` are resolved as dot-segments, and
+    /// so would address the tenant's metadata as a whole rather than a single
+    /// key.
+    pub async fn delete_tenant_metadata<I>(&self, id: I, key: &str) -> Result<Tenant, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        check_metadata_key(key)?;
         let req = self.build_request(
             Method::DELETE,
             TENANT_PATH
@@ -135,4 +690,65 @@ impl Client {
         let res = self.send_request(req).await?;
         Ok(res)
     }
+
+    /// Removes a key/value from a tenant's metadata, if the key is present.
+    ///
+    /// Returns `true` if the key was removed or `false` if the tenant's
+    /// metadata did not contain the key, in which case no delete request is
+    /// sent. A `404 Not Found` error from the delete request, as when the key
+    /// is concurrently removed, also results in `false`. This makes the
+    /// operation suitable for idempotent cleanup.
+    ///
+    /// The tenant is fetched to determine whether the key is present, so a
+    /// tenant that does not exist still results in a `404 Not Found` error.
+    /// Keys are validated as in [`Client::delete_tenant_metadata`].
+    pub async fn delete_tenant_metadata_if_present<I>(
+        &self,
+        id: I,
+        key: &str,
+    ) -> Result<bool, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let id = id.into();
+        check_metadata_key(key)?;
+        let tenant = self.get_tenant(id).await?;
+        if tenant.metadata.get(key).is_none() {
+            return Ok(false);
+        }
+        // The key may have been removed since the tenant was fetched.
+        match self.delete_tenant_metadata(id, key).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.status_code() == Some(StatusCode::NOT_FOUND) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Rejects metadata keys that cannot be expressed as a path segment.
+///
+/// An empty key would address the tenant's metadata as a whole rather than a
+/// single key, and URL parsing resolves the keys `.` and `..` as dot-segments
+/// rather than encoding them, with the same effect.
+fn check_metadata_key(key: &str) -> Result<(), Error> {
+    if matches!(key, "" | "." | "..") {
+        return Err(Error::InvalidMetadataKey { key: key.into() });
+    }
+    Ok(())
+}
+
+/// Extracts the tenant from a response to a get tenant request.
+fn single_tenant(mut res: Vec<Tenant>) -> Result<Tenant, Error> {
+    // Frontegg returns a list containing the tenant, rather than the tenant
+    // itself, and returns an empty list if the tenant does not exist.
+    match res.len() {
+        0 => Err(Error::Api(ApiError::new(
+            StatusCode::NOT_FOUND,
+            vec!["Tenant not found".to_string()],
+        ))),
+        1 => Ok(res.remove(0)),
+        n => Err(Error::Decode(serde::de::Error::custom(format!(
+            "expected at most one tenant, but got {n}"
+        )))),
+    }
 }
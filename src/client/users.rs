@@ -13,33 +13,71 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
 use async_stream::try_stream;
+use chrono::Utc;
 use futures_core::stream::Stream;
-use reqwest::Method;
+use futures_util::stream::{self, TryStreamExt};
+use reqwest::{Method, StatusCode};
+use serde::de::{self, DeserializeOwned};
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
+use tokio::time::{sleep, timeout_at, Instant};
 use uuid::Uuid;
 
 use crate::client::roles::{Permission, Role};
-use crate::client::Client;
+use crate::client::tenants::Tenant;
+use crate::client::{Client, SortOrder};
 use crate::error::Error;
+use crate::id::{TenantId, UserId};
+use crate::secret::SecretString;
 use crate::serde::{Empty, Paginated};
-use crate::util::{RequestBuilderExt, StrIteratorExt};
+use crate::util::{self, RequestBuilderExt, StrIteratorExt};
 
 const USER_PATH: [&str; 4] = ["identity", "resources", "users", "v1"];
 const VENDOR_USER_PATH: [&str; 5] = ["identity", "resources", "vendor-only", "users", "v1"];
+const TENANT_USER_PATH: [&str; 5] = ["identity", "resources", "tenants", "users", "v1"];
+const IMPERSONATION_PATH: [&str; 4] = ["identity", "resources", "impersonation", "v1"];
 
 /// Configuration for the [`Client::list_users`] operation.
 #[derive(Debug, Clone)]
 pub struct UserListConfig {
-    tenant_id: Option<Uuid>,
+    tenant_ids: Vec<TenantId>,
+    application_id: Option<Uuid>,
+    role_ids: Vec<Uuid>,
+    created_after: Option<OffsetDateTime>,
+    updated_after: Option<OffsetDateTime>,
+    created_at_order: Option<SortOrder>,
+    include_roles: Option<bool>,
+    include_sub_tenants: Option<bool>,
+    max_results: Option<u64>,
+    total_timeout: Option<Duration>,
+    dedupe_by_id: bool,
+    extra_query_params: Vec<(String, String)>,
+    start_page: u64,
     page_size: u64,
 }
 
 impl Default for UserListConfig {
     fn default() -> UserListConfig {
         UserListConfig {
-            tenant_id: None,
+            tenant_ids: vec![],
+            application_id: None,
+            role_ids: vec![],
+            created_after: None,
+            updated_after: None,
+            created_at_order: None,
+            include_roles: None,
+            include_sub_tenants: None,
+            max_results: None,
+            total_timeout: None,
+            dedupe_by_id: false,
+            extra_query_params: vec![],
+            start_page: 0,
             page_size: 50,
         }
     }
@@ -48,9 +86,113 @@ impl Default for UserListConfig {
 impl UserListConfig {
     /// Sets the tenant ID to filter users to.
     ///
-    /// If this method is not called, users for all tenants are returned.
-    pub fn tenant_id(mut self, tenant_id: Uuid) -> Self {
-        self.tenant_id = Some(tenant_id);
+    /// If neither this method nor [`UserListConfig::tenant_ids`] is called,
+    /// users for all tenants are returned.
+    pub fn tenant_id<T>(mut self, tenant_id: T) -> Self
+    where
+        T: Into<TenantId>,
+    {
+        self.tenant_ids = vec![tenant_id.into()];
+        self
+    }
+
+    /// Sets the tenant IDs to filter users to.
+    ///
+    /// Frontegg does not support listing the users of multiple tenants in one
+    /// request, so the users of each tenant are listed in turn. A user that
+    /// belongs to several of the specified tenants is returned only once,
+    /// when it is first encountered.
+    pub fn tenant_ids<I>(mut self, tenant_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<TenantId>,
+    {
+        self.tenant_ids = tenant_ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the application ID to filter users to.
+    ///
+    /// If this method is not called, users for all applications are
+    /// returned.
+    pub fn application_id(mut self, application_id: Uuid) -> Self {
+        self.application_id = Some(application_id);
+        self
+    }
+
+    /// Sets the role IDs to filter users to.
+    ///
+    /// Only users that hold at least one of the specified roles are returned.
+    /// If a tenant ID is also set, only roles held within that tenant are
+    /// considered; otherwise, roles held in any tenant are considered.
+    ///
+    /// If this method is not called, users are not filtered by role.
+    pub fn role_ids<I>(mut self, role_ids: I) -> Self
+    where
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.role_ids = role_ids.into_iter().collect();
+        self
+    }
+
+    /// Filters to users created after the specified time.
+    ///
+    /// The time may have any offset. It is converted to UTC before being sent
+    /// to Frontegg, so only the instant it represents is significant.
+    pub fn created_after(mut self, time: OffsetDateTime) -> Self {
+        self.created_after = Some(time);
+        self
+    }
+
+    /// Filters to users updated after the specified time.
+    ///
+    /// As with [`UserListConfig::created_after`], only the instant the time
+    /// represents is significant.
+    pub fn updated_after(mut self, time: OffsetDateTime) -> Self {
+        self.updated_after = Some(time);
+        self
+    }
+
+    /// Sorts users by creation time in the specified order.
+    ///
+    /// Sorting is performed by the Frontegg API. If this method is not called,
+    /// the order in which users are returned is unspecified and may not be
+    /// stable across pages.
+    pub fn sort_by_created_at(mut self, order: SortOrder) -> Self {
+        self.created_at_order = Some(order);
+        self
+    }
+
+    /// Sets whether to embed each user's roles in their tenant bindings.
+    ///
+    /// If this method is not called, whether roles are embedded is determined
+    /// by the Frontegg API.
+    pub fn include_roles(mut self, include: bool) -> Self {
+        self.include_roles = Some(include);
+        self
+    }
+
+    /// Sets whether to include the users of the sub-tenants of the tenants
+    /// that users are filtered to.
+    ///
+    /// If this method is not called, whether sub-tenants are included is
+    /// determined by the Frontegg API.
+    pub fn include_sub_tenants(mut self, include: bool) -> Self {
+        self.include_sub_tenants = Some(include);
+        self
+    }
+
+    /// Sets the zero-indexed page at which to start listing users.
+    ///
+    /// Useful for resuming a long-running listing from a checkpoint. The page
+    /// size must match the page size used when the checkpoint was taken.
+    ///
+    /// If multiple tenants are specified via [`UserListConfig::tenant_ids`],
+    /// the start page applies to the listing of each tenant's users.
+    ///
+    /// Defaults to zero.
+    pub fn start_page(mut self, start_page: u64) -> Self {
+        self.start_page = start_page;
         self
     }
 
@@ -59,10 +201,77 @@ impl UserListConfig {
         self.page_size = page_size;
         self
     }
+
+    /// Sets the maximum number of users to return.
+    ///
+    /// Once this many users have been returned, the listing terminates, even
+    /// if more users are available. This is a safety valve against
+    /// unexpectedly large listings, e.g., when collecting the users of a
+    /// large workspace into memory. The limit is enforced by the client, not
+    /// by Frontegg, so the final page fetched may contain users that are
+    /// discarded.
+    ///
+    /// If unset, all users are returned.
+    pub fn max_results(mut self, max_results: u64) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Sets the maximum time the entire listing may take.
+    ///
+    /// The timeout starts when the stream is first polled and covers all
+    /// pages, including any time spent waiting for the consumer to poll the
+    /// stream. Once it elapses, the in-flight request, if any, is canceled
+    /// and the stream terminates with an [`Error::TimedOut`] error.
+    ///
+    /// This is distinct from the per-request timeout configured via
+    /// [`ClientBuilder::with_timeout`], which bounds each individual request
+    /// and so does not bound a listing that spans many slow pages.
+    ///
+    /// If unset, the listing is not bounded in time.
+    ///
+    /// [`ClientBuilder::with_timeout`]: crate::ClientBuilder::with_timeout
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether to return each user at most once.
+    ///
+    /// Frontegg may return a user that belongs to multiple tenants once per
+    /// tenant. When enabled, occurrences of a user after the first are
+    /// skipped. Their tenant bindings are not merged into the first
+    /// occurrence, as it has already been returned.
+    ///
+    /// Deduplication requires tracking the ID of every user returned, so
+    /// memory use grows with the size of the listing, at roughly 16 bytes per
+    /// user plus overhead. Users are always deduplicated when multiple
+    /// tenants are specified via [`UserListConfig::tenant_ids`].
+    ///
+    /// Defaults to `false`.
+    pub fn dedupe_by_id(mut self, dedupe_by_id: bool) -> Self {
+        self.dedupe_by_id = dedupe_by_id;
+        self
+    }
+
+    /// Adds a query parameter to send with each request, in addition to
+    /// those configured by other methods.
+    ///
+    /// This is an escape hatch for query parameters that Frontegg supports
+    /// but this crate does not yet expose. The parameter is passed through
+    /// without validation, and may be specified multiple times.
+    pub fn extra_query_param<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.extra_query_params.push((key.into(), value.into()));
+        self
+    }
 }
 
 /// The subset of [`User`] used in create requests.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserRequest<'a> {
     /// The ID of the tenant to which the user will belong.
@@ -75,11 +284,190 @@ pub struct UserRequest<'a> {
     /// Arbitrary metadata to attach to the user.
     pub metadata: serde_json::Value,
     /// Whether to skip sending an invitation email to the user.
-    pub skip_invite_email: bool,
+    ///
+    /// If unset, the default configured via
+    /// [`ClientBuilder::with_default_skip_invite_email`] applies.
+    ///
+    /// [`ClientBuilder::with_default_skip_invite_email`]: crate::ClientBuilder::with_default_skip_invite_email
+    pub skip_invite_email: Option<bool>,
+    /// The ID of the application to which the user will be assigned.
+    ///
+    /// If unset, the user is not scoped to any particular application.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<Uuid>,
+}
+
+impl<'a> UserRequest<'a> {
+    /// Returns a builder for a request to create a user with the specified
+    /// tenant, name, and email.
+    ///
+    /// The required fields are passed up front, so a request built via the
+    /// builder cannot omit them.
+    pub fn builder<T>(tenant_id: T, name: &'a str, email: &'a str) -> UserRequestBuilder<'a>
+    where
+        T: Into<TenantId>,
+    {
+        UserRequestBuilder {
+            req: UserRequest {
+                tenant_id: tenant_id.into().as_uuid(),
+                name,
+                email,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the metadata to attach to the user from a strongly typed value.
+    ///
+    /// Describing the metadata with a type, rather than an arbitrary
+    /// [`serde_json::Value`], allows mistakes like misspelled keys to be
+    /// caught at compile time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::Serialize;
+    /// # use frontegg::UserRequest;
+    /// # fn example() -> Result<(), serde_json::Error> {
+    /// #[derive(Serialize)]
+    /// struct UserMetadata {
+    ///     plan: String,
+    /// }
+    ///
+    /// let user = UserRequest {
+    ///     name: "Jane Doe",
+    ///     email: "jane@example.com",
+    ///     ..Default::default()
+    /// }
+    /// .with_metadata(&UserMetadata {
+    ///     plan: "enterprise".into(),
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_metadata<M>(mut self, metadata: &M) -> Result<Self, serde_json::Error>
+    where
+        M: Serialize,
+    {
+        self.metadata = serde_json::to_value(metadata)?;
+        Ok(self)
+    }
+}
+
+/// A builder for a [`UserRequest`].
+///
+/// Created via [`UserRequest::builder`].
+#[derive(Debug, Clone)]
+pub struct UserRequestBuilder<'a> {
+    req: UserRequest<'a>,
+}
+
+impl<'a> UserRequestBuilder<'a> {
+    /// Sets the metadata to attach to the user.
+    pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.req.metadata = metadata;
+        self
+    }
+
+    /// Sets whether to skip sending an invitation email to the user.
+    ///
+    /// If this method is not called, the default configured via
+    /// [`ClientBuilder::with_default_skip_invite_email`] applies.
+    ///
+    /// [`ClientBuilder::with_default_skip_invite_email`]: crate::ClientBuilder::with_default_skip_invite_email
+    pub fn skip_invite_email(mut self, skip: bool) -> Self {
+        self.req.skip_invite_email = Some(skip);
+        self
+    }
+
+    /// Sets the ID of the application to which the user will be assigned.
+    pub fn application_id(mut self, application_id: Uuid) -> Self {
+        self.req.application_id = Some(application_id);
+        self
+    }
+
+    /// Builds the request.
+    pub fn build(self) -> UserRequest<'a> {
+        self.req
+    }
+}
+
+/// An owned version of [`UserRequest`].
+///
+/// Useful when building requests from owned data, e.g., when constructing
+/// many requests up front. Use [`OwnedUserRequest::as_request`] to borrow a
+/// [`UserRequest`] to pass to [`Client::create_user`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedUserRequest {
+    /// The ID of the tenant to which the user will belong.
+    #[serde(skip)]
+    pub tenant_id: Uuid,
+    /// The name of the user.
+    pub name: String,
+    /// The email for the user.
+    pub email: String,
+    /// Arbitrary metadata to attach to the user.
+    pub metadata: serde_json::Value,
+    /// Whether to skip sending an invitation email to the user.
+    ///
+    /// If unset, the default configured via
+    /// [`ClientBuilder::with_default_skip_invite_email`] applies.
+    ///
+    /// [`ClientBuilder::with_default_skip_invite_email`]: crate::ClientBuilder::with_default_skip_invite_email
+    pub skip_invite_email: Option<bool>,
+    /// The ID of the application to which the user will be assigned.
+    ///
+    /// If unset, the user is not scoped to any particular application.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<Uuid>,
+}
+
+impl OwnedUserRequest {
+    /// Borrows this request as a [`UserRequest`].
+    pub fn as_request(&self) -> UserRequest<'_> {
+        UserRequest {
+            tenant_id: self.tenant_id,
+            name: &self.name,
+            email: &self.email,
+            metadata: self.metadata.clone(),
+            skip_invite_email: self.skip_invite_email,
+            application_id: self.application_id,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedUserRequest> for UserRequest<'a> {
+    fn from(req: &'a OwnedUserRequest) -> UserRequest<'a> {
+        req.as_request()
+    }
+}
+
+impl From<UserRequest<'_>> for OwnedUserRequest {
+    fn from(req: UserRequest<'_>) -> OwnedUserRequest {
+        OwnedUserRequest {
+            tenant_id: req.tenant_id,
+            name: req.name.into(),
+            email: req.email.into(),
+            metadata: req.metadata,
+            skip_invite_email: req.skip_invite_email,
+            application_id: req.application_id,
+        }
+    }
+}
+
+/// A token that permits acting as a user, as returned by
+/// [`Client::impersonate_user`].
+#[derive(Debug, Clone)]
+pub struct ImpersonationToken {
+    /// The bearer token.
+    pub token: SecretString,
+    /// The time at which the token expires.
+    pub expires_at: OffsetDateTime,
 }
 
 /// The subset of a [`User`] returned by [`Client::create_user`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatedUser {
     /// The ID of the user.
@@ -90,21 +478,43 @@ pub struct CreatedUser {
     pub email: String,
     /// Arbitrary metadata that is attached to the user.
     #[serde(default = "crate::serde::empty_json_object")]
-    #[serde(deserialize_with = "crate::serde::nested_json::deserialize")]
+    #[serde(with = "crate::serde::nested_json")]
     pub metadata: serde_json::Value,
     /// The roles to which this user belongs.
+    #[serde(default)]
     pub roles: Vec<Role>,
     /// The permissions which this user holds.
+    #[serde(default)]
     pub permissions: Vec<Permission>,
     /// The time at which the user was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
 }
 
 /// The subset of a [`User`] returned by a `frontegg.user.*` webhook event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Some fields are absent from certain events and are therefore optional.
+/// For MFA events, [`DisabledMfaEvent`] and [`EnrolledMfaEvent`] expose
+/// exactly the fields present in each event.
+///
+/// [`DisabledMfaEvent`]: crate::DisabledMfaEvent
+/// [`EnrolledMfaEvent`]: crate::EnrolledMfaEvent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebhookUser {
+    /// The fields present in every `frontegg.user.*` webhook event.
+    #[serde(flatten)]
+    pub profile: WebhookUserProfile,
+    /// The IDs of all tenants for the user. Missing on frontegg.user.disabledMFA events.
+    pub tenant_ids: Option<Vec<Uuid>>,
+    /// The tenants to which this user belongs. Missing on frontegg.user.disabledMFA events.
+    pub tenants: Option<Vec<WebhookTenantBinding>>,
+}
+
+/// The fields of a [`User`] present in every `frontegg.user.*` webhook event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookUserProfile {
     /// The ID of the user.
     pub id: Uuid,
     /// The name of the user.
@@ -113,14 +523,16 @@ pub struct WebhookUser {
     pub email: String,
     /// Arbitrary metadata that is attached to the user.
     #[serde(default = "crate::serde::empty_json_object")]
-    #[serde(deserialize_with = "crate::serde::nested_json::deserialize")]
+    #[serde(with = "crate::serde::nested_json")]
     pub metadata: serde_json::Value,
     /// The roles to which this user belongs.
+    #[serde(default)]
     pub roles: Vec<Role>,
     /// The permissions which this user holds.
+    #[serde(default)]
     pub permissions: Vec<Permission>,
     /// The time at which the user was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
     /// The activation status of the user for the tenant.
     pub activated_for_tenant: Option<bool>,
@@ -142,16 +554,12 @@ pub struct WebhookUser {
     pub sub: Uuid,
     /// The ID of the tenant of the user.
     pub tenant_id: Uuid,
-    /// The IDs of all tenants for the user. Missing on frontegg.user.disabledMFA events.
-    pub tenant_ids: Option<Vec<Uuid>>,
-    /// The tenants to which this user belongs. Missing on frontegg.user.disabledMFA events.
-    pub tenants: Option<Vec<WebhookTenantBinding>>,
     /// The verified status of the user.
     pub verified: Option<bool>,
 }
 
 /// A Frontegg user.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
     /// The ID of the user.
@@ -162,19 +570,68 @@ pub struct User {
     pub email: String,
     /// Arbitrary metadata that is attached to the user.
     #[serde(default = "crate::serde::empty_json_object")]
-    #[serde(deserialize_with = "crate::serde::nested_json::deserialize")]
+    #[serde(with = "crate::serde::nested_json")]
     pub metadata: serde_json::Value,
     /// The tenants to which this user belongs.
     pub tenants: Vec<TenantBinding>,
     /// The time at which the user was created.
-    #[serde(with = "time::serde::rfc3339")]
+    #[serde(with = "crate::serde::timestamp")]
     pub created_at: OffsetDateTime,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl User {
+    /// Deserializes the user's metadata into the specified type.
+    ///
+    /// See [`Tenant::metadata_as`] for an example.
+    ///
+    /// [`Tenant::metadata_as`]: crate::Tenant::metadata_as
+    pub fn metadata_as<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        T::deserialize(&self.metadata)
+    }
+
+    /// Returns the roles the user holds in the specified tenant.
+    ///
+    /// Returns `None` if the user does not belong to the tenant.
+    pub fn roles_in_tenant<T>(&self, tenant_id: T) -> Option<&[Role]>
+    where
+        T: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into().as_uuid();
+        self.tenants
+            .iter()
+            .find(|t| t.tenant_id == tenant_id)
+            .map(|t| &*t.roles)
+    }
+
+    /// Returns the roles the user holds in any tenant.
+    ///
+    /// A role held in multiple tenants is returned only once.
+    pub fn all_roles(&self) -> Vec<&Role> {
+        let mut seen = HashSet::new();
+        self.tenants
+            .iter()
+            .flat_map(|t| &t.roles)
+            .filter(|r| seen.insert(r.id))
+            .collect()
+    }
+
+    /// Returns [`User::created_at`] as a [`chrono::DateTime`].
+    pub fn created_at_chrono(&self) -> chrono::DateTime<Utc> {
+        util::to_chrono(self.created_at)
+    }
 }
 
 /// Binds a [`User`] to a [`Tenant`] for a `frontegg.user.*` webhook event
 ///
 /// [`Tenant`]: crate::client::tenants::Tenant
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebhookTenantBinding {
     /// The ID of the tenant.
@@ -186,7 +643,7 @@ pub struct WebhookTenantBinding {
 /// Binds a [`User`] to a [`Tenant`].
 ///
 /// [`Tenant`]: crate::client::tenant::Tenant
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TenantBinding {
     /// The ID of the tenant.
@@ -196,37 +653,199 @@ pub struct TenantBinding {
 }
 
 impl Client {
-    /// Lists users, either for all tenants or for a single tenant.
+    /// Lists users, either for all tenants or for the specified tenants.
     ///
     /// The underlying API call is paginated. The returned stream will fetch
-    /// additional pages as it is consumed.
+    /// additional pages as it is consumed. Dropping the stream cancels any
+    /// request that is in flight and no further pages are fetched. To resume
+    /// a listing later, see [`UserListConfig::start_page`].
+    ///
+    /// Unless [`UserListConfig::sort_by_created_at`] is specified, the order
+    /// in which users are returned is unspecified.
+    ///
+    /// Frontegg paginates by offset, so users created or deleted while the
+    /// listing is in progress shift other users between pages. If users are
+    /// deleted concurrently, users that exist for the entire listing may be
+    /// skipped; if users are created concurrently, users may be returned more
+    /// than once. Absent concurrent changes, each user is returned exactly
+    /// once. In either case, the listing terminates once a page reaches the
+    /// total number of users reported by Frontegg at the time, or a page is
+    /// empty.
     pub fn list_users(
         &self,
         config: UserListConfig,
-    ) -> impl Stream<Item = Result<User, Error>> + '_ {
+    ) -> impl Stream<Item = Result<User, Error>> + Send + '_ {
+        self.list_user_pages(config)
+            .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Like [`Client::list_users`], but yields one item per page of users
+    /// fetched.
+    ///
+    /// Each page contains at most the number of users configured via
+    /// [`UserListConfig::page_size`]. When listing the users of multiple
+    /// tenants, users already returned for a previous tenant are omitted, so
+    /// pages may contain fewer users.
+    pub fn list_user_pages(
+        &self,
+        config: UserListConfig,
+    ) -> impl Stream<Item = Result<Vec<User>, Error>> + Send + '_ {
         try_stream! {
-            let mut page = 0;
-            loop {
-                let mut req = self.build_request(Method::GET, USER_PATH);
-                if let Some(tenant_id) = config.tenant_id {
-                    req = req.tenant(tenant_id);
-                }
-                let req = req.query(&[
-                    ("_limit", &*config.page_size.to_string()),
-                    ("_offset", &*page.to_string())
-                ]);
-                let res: Paginated<User> = self.send_request(req).await?;
-                for user in res.items {
-                    yield user;
-                }
-                page += 1;
-                if page >= res.metadata.total_pages {
+            let tenant_ids: Vec<_> = match config.tenant_ids.as_slice() {
+                [] => vec![None],
+                tenant_ids => tenant_ids.iter().copied().map(Some).collect(),
+            };
+            // Only track the users seen so far when they could be duplicated
+            // across tenants or deduplication is requested.
+            let mut seen = (tenant_ids.len() > 1 || config.dedupe_by_id).then(HashSet::new);
+            let mut remaining = config.max_results;
+            // A timeout too large to represent as a deadline has no deadline.
+            let deadline = config.total_timeout.and_then(|timeout| {
+                Some((Instant::now().checked_add(timeout)?, timeout))
+            });
+            'tenants: for tenant_id in tenant_ids {
+                if remaining == Some(0) {
                     break;
                 }
+                let mut page = config.start_page;
+                loop {
+                    let mut req = self.build_request(Method::GET, USER_PATH);
+                    if let Some(tenant_id) = tenant_id {
+                        req = req.tenant(tenant_id);
+                    }
+                    if let Some(application_id) = config.application_id {
+                        req = req.application(application_id);
+                    }
+                    if !config.role_ids.is_empty() {
+                        let role_ids: Vec<_> =
+                            config.role_ids.iter().map(|id| id.to_string()).collect();
+                        req = req.query(&[("_roleIds", role_ids.join(","))]);
+                    }
+                    for (key, time) in [
+                        ("_createdAfter", config.created_after),
+                        ("_updatedAfter", config.updated_after),
+                    ] {
+                        if let Some(time) = time {
+                            let time = time.to_offset(UtcOffset::UTC);
+                            // RFC 3339 cannot represent every valid time,
+                            // like times in negative years.
+                            let time = time.format(&Rfc3339).map_err(|e| {
+                                Error::Decode(serde::ser::Error::custom(format!(
+                                    "invalid {key} time: {e}"
+                                )))
+                            })?;
+                            req = req.query(&[(key, time)]);
+                        }
+                    }
+                    for (key, include) in [
+                        ("_includeRoles", config.include_roles),
+                        ("_includeSubTenants", config.include_sub_tenants),
+                    ] {
+                        if let Some(include) = include {
+                            req = req.query(&[(key, include)]);
+                        }
+                    }
+                    if let Some(order) = config.created_at_order {
+                        req = req.query(&[("_sortBy", "createdAt"), ("_order", order.as_str())]);
+                    }
+                    let req = req.query(&config.extra_query_params).query(&[
+                        ("_limit", &*config.page_size.to_string()),
+                        ("_offset", &*page.to_string())
+                    ]);
+                    let mut res: Paginated<User> = match deadline {
+                        Some((deadline, timeout)) => timeout_at(deadline, self.send_request(req))
+                            .await
+                            .map_err(|_| Error::TimedOut { timeout })??,
+                        None => self.send_request(req).await?,
+                    };
+                    // Users created or deleted during the listing change the
+                    // total page count between requests, so rather than
+                    // relying on the page count, continue until a page
+                    // reaches the reported total number of users or is empty.
+                    // Fall back to the page count if no total is reported.
+                    let fetched = page * config.page_size + res.items.len() as u64;
+                    let done = res.items.is_empty()
+                        || match res.metadata.total_items {
+                            Some(total_items) => fetched >= total_items,
+                            None => page + 1 >= res.metadata.total_pages,
+                        };
+                    if let Some(seen) = &mut seen {
+                        res.items.retain(|user| seen.insert(user.id));
+                    }
+                    if let Some(remaining) = &mut remaining {
+                        res.items.truncate(usize::try_from(*remaining).unwrap_or(usize::MAX));
+                        *remaining -= res.items.len() as u64;
+                    }
+                    yield res.items;
+                    if remaining == Some(0) {
+                        break 'tenants;
+                    }
+                    if done {
+                        break;
+                    }
+                    page += 1;
+                }
+            }
+        }
+    }
+
+    /// Lists all users, each alongside the tenants to which it belongs.
+    ///
+    /// The list of tenants is fetched once, before the first user is
+    /// yielded, and is not refreshed as the stream is consumed. The tenants
+    /// are therefore a snapshot as of the start of the export: a tenant
+    /// created while the export is in progress is omitted from the tenants
+    /// of its users, and a tenant deleted while the export is in progress is
+    /// reported as it was when the snapshot was taken.
+    ///
+    /// Users are fetched as described in [`Client::list_users`].
+    pub fn export_users_with_tenants(
+        &self,
+    ) -> impl Stream<Item = Result<(User, Vec<Tenant>), Error>> + Send + '_ {
+        try_stream! {
+            let tenants: HashMap<_, _> = self
+                .list_tenants()
+                .await?
+                .into_iter()
+                .map(|tenant| (tenant.id, tenant))
+                .collect();
+            for await user in self.list_users(UserListConfig::default()) {
+                let user = user?;
+                let user_tenants = user
+                    .tenants
+                    .iter()
+                    .filter_map(|binding| tenants.get(&binding.tenant_id).cloned())
+                    .collect();
+                yield (user, user_tenants);
             }
         }
     }
 
+    /// Counts the users that belong to the specified tenant.
+    ///
+    /// The count includes users that are deactivated for the tenant, as
+    /// Frontegg does not distinguish them when listing a tenant's users.
+    ///
+    /// Only a single user is fetched from the API, so this is considerably
+    /// cheaper than counting the users returned by [`Client::list_users`].
+    ///
+    /// Returns an [`Error::Decode`] error if Frontegg does not report the
+    /// total number of users.
+    pub async fn count_tenant_users<I>(&self, tenant_id: I) -> Result<u64, Error>
+    where
+        I: Into<TenantId>,
+    {
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::GET, USER_PATH);
+        let req = req.tenant(tenant_id);
+        let req = req.query(&[("_limit", "1"), ("_offset", "0")]);
+        let res: Paginated<User> = self.send_request(req).await?;
+        res.metadata
+            .total_items
+            .ok_or_else(|| Error::Decode(de::Error::missing_field("_metadata.totalItems")))
+    }
+
     /// Creates a new user.
     ///
     /// Only partial information about the created user is returned. To fetch
@@ -234,22 +853,439 @@ impl Client {
     pub async fn create_user(&self, user: &UserRequest<'_>) -> Result<CreatedUser, Error> {
         let req = self.build_request(Method::POST, USER_PATH);
         let req = req.tenant(user.tenant_id);
-        let req = req.json(user);
+        let req = req.json(&UserRequest {
+            skip_invite_email: Some(
+                user.skip_invite_email
+                    .unwrap_or(self.default_skip_invite_email),
+            ),
+            ..user.clone()
+        });
         let res = self.send_request(req).await?;
         Ok(res)
     }
 
     /// Gets a user by ID.
-    pub async fn get_user(&self, id: Uuid) -> Result<User, Error> {
+    pub async fn get_user<I>(&self, id: I) -> Result<User, Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
         let req = self.build_request(Method::GET, VENDOR_USER_PATH.chain_one(id));
         let res = self.send_request(req).await?;
         Ok(res)
     }
 
+    /// Gets a user by ID, along with the JSON representation of the user
+    /// returned by Frontegg.
+    ///
+    /// Useful for archiving or forwarding the user exactly as Frontegg
+    /// reported it, including any fields not represented in [`User`].
+    pub async fn get_user_raw<I>(&self, id: I) -> Result<(User, serde_json::Value), Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
+        let req = self.build_request(Method::GET, VENDOR_USER_PATH.chain_one(id));
+        let raw: serde_json::Value = self.send_request(req).await?;
+        let user = serde_json::from_value(raw.clone())?;
+        Ok((user, raw))
+    }
+
+    /// Gets a user by ID, if the user exists.
+    ///
+    /// Returns `None` rather than a `404 Not Found` error if the user does not
+    /// exist.
+    pub async fn try_get_user<I>(&self, id: I) -> Result<Option<User>, Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
+        match self.get_user(id).await {
+            Ok(user) => Ok(Some(user)),
+            Err(Error::Api(e)) if e.status_code == StatusCode::NOT_FOUND => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets a user by ID, waiting up to `timeout` for the user to appear.
+    ///
+    /// Frontegg is eventually consistent, so a user may not be visible
+    /// immediately after it is created via [`Client::create_user`]. This
+    /// method retries `404 Not Found` errors with exponential backoff until
+    /// the user is found or the timeout elapses, at which point the last
+    /// `404 Not Found` error is returned. Other errors are returned
+    /// immediately.
+    pub async fn get_user_eventually<I>(&self, id: I, timeout: Duration) -> Result<User, Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
+        // A timeout too large to represent as a deadline has no deadline.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut backoff = Duration::from_millis(100);
+        loop {
+            match self.get_user(id).await {
+                Err(Error::Api(e)) if e.status_code == StatusCode::NOT_FOUND => {
+                    let now = Instant::now();
+                    match deadline {
+                        Some(deadline) if now >= deadline => return Err(Error::Api(e)),
+                        Some(deadline) => sleep(backoff.min(deadline - now)).await,
+                        None => sleep(backoff).await,
+                    }
+                    backoff = (backoff * 2).min(Duration::from_secs(2));
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Gets the permissions that a user holds in any tenant.
+    ///
+    /// The permissions are the union of the permissions granted by each role
+    /// to which the user belongs, across all of the user's tenants, and are
+    /// returned in ascending order by ID without duplicates.
+    ///
+    /// In addition to fetching the user, this method lists all roles and all
+    /// permissions in the workspace to resolve the user's roles to
+    /// permissions, so it makes at least three API calls.
+    pub async fn get_user_effective_permissions<I>(&self, id: I) -> Result<Vec<Permission>, Error>
+    where
+        I: Into<UserId>,
+    {
+        let user = self.get_user(id).await?;
+        let role_ids: HashSet<_> = user
+            .tenants
+            .iter()
+            .flat_map(|binding| &binding.roles)
+            .map(|role| role.id)
+            .collect();
+        if role_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let permission_ids: HashSet<_> = self
+            .list_roles()
+            .await?
+            .into_iter()
+            .filter(|role| role_ids.contains(&role.id))
+            .flat_map(|role| role.permission_ids)
+            .collect();
+        let mut permissions: Vec<_> = self
+            .list_permissions()
+            .await?
+            .into_iter()
+            .filter(|permission| permission_ids.contains(&permission.id))
+            .collect();
+        permissions.sort();
+        permissions.dedup();
+        Ok(permissions)
+    }
+
+    /// Gets a user by ID, unless the user is unchanged.
+    ///
+    /// If `etag` is specified and matches the user's current entity tag,
+    /// returns `None`. Otherwise, returns the user and its current entity tag,
+    /// if Frontegg reported one. Callers are responsible for storing the
+    /// entity tag to pass to subsequent calls.
+    pub async fn get_user_conditional<I>(
+        &self,
+        id: I,
+        etag: Option<&str>,
+    ) -> Result<Option<(User, Option<String>)>, Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
+        let req = self.build_request(Method::GET, VENDOR_USER_PATH.chain_one(id));
+        self.send_conditional_request(req, etag).await
+    }
+
+    /// Gets a user by ID as seen from within the specified tenant.
+    ///
+    /// A user's roles can differ between the tenants to which the user
+    /// belongs. The returned user's tenant bindings reflect only the specified
+    /// tenant.
+    pub async fn get_user_in_tenant<U, T>(&self, id: U, tenant_id: T) -> Result<User, Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let id = id.into();
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::GET, USER_PATH.chain_one(id));
+        let req = req.tenant(tenant_id);
+        let mut res: User = self.send_request(req).await?;
+        // Frontegg may report the user's bindings to other tenants too.
+        res.tenants.retain(|t| t.tenant_id == tenant_id.as_uuid());
+        Ok(res)
+    }
+
+    /// Re-fetches a user, updating it in place.
+    ///
+    /// Useful after mutating a user, e.g., via [`Client::assign_user_roles`],
+    /// to observe the effect of the mutation.
+    ///
+    /// Frontegg does not guarantee that mutations are immediately visible to
+    /// subsequent reads, so a user refreshed immediately after a mutation may
+    /// not yet reflect it.
+    pub async fn refresh_user(&self, user: &mut User) -> Result<(), Error> {
+        *user = self.get_user(user.id).await?;
+        Ok(())
+    }
+
+    /// Assigns roles to a user in the specified tenant.
+    ///
+    /// Roles the user already holds in the tenant are retained.
+    pub async fn assign_user_roles<U, T>(
+        &self,
+        user_id: U,
+        tenant_id: T,
+        role_ids: &[Uuid],
+    ) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(
+            Method::POST,
+            USER_PATH.chain_one(user_id).chain_one("roles"),
+        );
+        let req = req.tenant(tenant_id);
+        let req = req.json(&json!({ "roleIds": role_ids }));
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+
+    /// Removes roles from a user in the specified tenant.
+    ///
+    /// Roles the user does not hold in the tenant are ignored.
+    pub async fn remove_user_roles<U, T>(
+        &self,
+        user_id: U,
+        tenant_id: T,
+        role_ids: &[Uuid],
+    ) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(
+            Method::DELETE,
+            USER_PATH.chain_one(user_id).chain_one("roles"),
+        );
+        let req = req.tenant(tenant_id);
+        let req = req.json(&json!({ "roleIds": role_ids }));
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+
+    /// Sets the roles a user holds in the specified tenant to exactly the
+    /// specified roles.
+    ///
+    /// Frontegg does not support replacing a user's roles in a single
+    /// request, so the user's current roles in the tenant are fetched and only
+    /// the difference is applied: roles that should no longer be held are
+    /// removed first, and then missing roles are assigned. The operation is
+    /// therefore not atomic. If it fails partway through, or if the user's
+    /// roles are modified concurrently, the user may be left with a subset of
+    /// the specified roles; retrying the operation converges on the specified
+    /// set.
+    pub async fn set_user_roles<U, T>(
+        &self,
+        user_id: U,
+        tenant_id: T,
+        role_ids: &[Uuid],
+    ) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        let user = self.get_user_in_tenant(user_id, tenant_id).await?;
+        let current: HashSet<Uuid> = user
+            .roles_in_tenant(tenant_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|role| role.id)
+            .collect();
+        let desired: HashSet<Uuid> = role_ids.iter().copied().collect();
+        let to_remove: Vec<Uuid> = current.difference(&desired).copied().collect();
+        let to_assign: Vec<Uuid> = desired.difference(&current).copied().collect();
+        if !to_remove.is_empty() {
+            self.remove_user_roles(user_id, tenant_id, &to_remove)
+                .await?;
+        }
+        if !to_assign.is_empty() {
+            self.assign_user_roles(user_id, tenant_id, &to_assign)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Deletes a user by ID.
-    pub async fn delete_user(&self, id: Uuid) -> Result<(), Error> {
+    pub async fn delete_user<I>(&self, id: I) -> Result<(), Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
         let req = self.build_request(Method::DELETE, USER_PATH.chain_one(id));
         let _: Empty = self.send_request(req).await?;
         Ok(())
     }
+
+    /// Deletes a user by ID, if the user exists.
+    ///
+    /// Returns `true` if the user was deleted or `false` if the user did not
+    /// exist.
+    pub async fn delete_user_if_exists<I>(&self, id: I) -> Result<bool, Error>
+    where
+        I: Into<UserId>,
+    {
+        let id = id.into();
+        match self.delete_user(id).await {
+            Ok(()) => Ok(true),
+            Err(Error::Api(e)) if e.status_code == StatusCode::NOT_FOUND => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Removes a user from the specified tenant.
+    ///
+    /// If the user belongs to no other tenant, Frontegg deletes the user.
+    pub async fn remove_user_from_tenant<U, T>(&self, user_id: U, tenant_id: T) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        let req = self.build_request(Method::DELETE, USER_PATH.chain_one(user_id));
+        let req = req.tenant(tenant_id);
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+
+    /// Activates a user in the specified tenant.
+    pub async fn activate_user<U, T>(&self, user_id: U, tenant_id: T) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        self.set_user_activation(user_id, tenant_id, true).await
+    }
+
+    /// Deactivates a user in the specified tenant.
+    ///
+    /// A deactivated user cannot log in to the tenant, but is otherwise
+    /// retained and can be reactivated via [`Client::activate_user`].
+    pub async fn deactivate_user<U, T>(&self, user_id: U, tenant_id: T) -> Result<(), Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        self.set_user_activation(user_id, tenant_id, false).await
+    }
+
+    /// Sends an email to a user asking them to verify their email address.
+    ///
+    /// Frontegg typically rejects the request with a `400 Bad Request` error
+    /// if the user's email address is already verified.
+    pub async fn send_verification_email<I>(&self, user_id: I) -> Result<(), Error>
+    where
+        I: Into<UserId>,
+    {
+        let user_id = user_id.into();
+        let req = self.build_request(
+            Method::POST,
+            USER_PATH
+                .chain_one(user_id)
+                .chain_one("verify")
+                .chain_one("resend"),
+        );
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+
+    /// Marks a user's email address as verified without requiring the user
+    /// to follow a verification link.
+    ///
+    /// As with [`Client::send_verification_email`], Frontegg typically
+    /// rejects the request with a `400 Bad Request` error if the user's email
+    /// address is already verified.
+    pub async fn verify_user_email<I>(&self, user_id: I) -> Result<(), Error>
+    where
+        I: Into<UserId>,
+    {
+        let user_id = user_id.into();
+        let req = self.build_request(
+            Method::POST,
+            USER_PATH.chain_one(user_id).chain_one("verify"),
+        );
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
+
+    /// Issues a token that permits acting as the specified user within the
+    /// specified tenant.
+    ///
+    /// This is a sensitive operation intended for troubleshooting. Actions
+    /// taken with the returned token are attributed to the impersonated user
+    /// in the Frontegg audit log, and the impersonation itself is recorded as
+    /// an audit event. Callers should record who requested the impersonation
+    /// and why, and should not retain the token beyond its immediate use.
+    ///
+    /// Fails with a `403 Forbidden` error if the vendor is not permitted to
+    /// impersonate the user.
+    pub async fn impersonate_user<U, T>(
+        &self,
+        user_id: U,
+        tenant_id: T,
+    ) -> Result<ImpersonationToken, Error>
+    where
+        U: Into<UserId>,
+        T: Into<TenantId>,
+    {
+        let user_id = user_id.into();
+        let tenant_id = tenant_id.into();
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ImpersonationResponse {
+            #[serde(alias = "accessToken")]
+            token: String,
+            expires_in: u64,
+        }
+
+        let req = self.build_request(Method::POST, IMPERSONATION_PATH);
+        let req = req.tenant(tenant_id);
+        let req = req.json(&json!({ "userId": user_id }));
+        let res: ImpersonationResponse = self.send_request(req).await?;
+        Ok(ImpersonationToken {
+            token: SecretString::new(res.token),
+            expires_at: OffsetDateTime::now_utc() + Duration::from_secs(res.expires_in),
+        })
+    }
+
+    async fn set_user_activation(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        activated: bool,
+    ) -> Result<(), Error> {
+        let req = self.build_request(
+            Method::PUT,
+            TENANT_USER_PATH.chain_one(user_id).chain_one("activate"),
+        );
+        let req = req.tenant(tenant_id);
+        let req = req.json(&json!({ "activated": activated }));
+        let _: Empty = self.send_request(req).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,66 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, Error};
+
+const VENDOR_CONFIG_PATH: [&str; 4] = ["identity", "resources", "configurations", "v1"];
+
+/// The identity configuration of a Frontegg workspace.
+///
+/// Fields that the workspace has not configured are `None`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VendorConfig {
+    /// The strategy by which users authenticate, e.g., `EmailAndPassword` or
+    /// `MagicLink`.
+    #[serde(default)]
+    pub auth_strategy: Option<String>,
+    /// Whether users may sign up on their own.
+    #[serde(default)]
+    pub allow_signups: Option<bool>,
+    /// Whether users may log in before verifying their email address.
+    #[serde(default)]
+    pub allow_not_verified_users_login: Option<bool>,
+    /// Whether API tokens are enabled.
+    #[serde(default)]
+    pub api_tokens_enabled: Option<bool>,
+    /// Whether permissions are enforced.
+    #[serde(default)]
+    pub force_permissions: Option<bool>,
+    /// The lifetime of access tokens, in seconds.
+    #[serde(default)]
+    pub default_token_expiration: Option<u64>,
+    /// The lifetime of refresh tokens, in seconds.
+    #[serde(default)]
+    pub default_refresh_token_expiration: Option<u64>,
+    /// Any additional fields reported by Frontegg that are not otherwise
+    /// represented, like password policy or branding settings.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Client {
+    /// Gets the identity configuration of the workspace.
+    pub async fn get_vendor_config(&self) -> Result<VendorConfig, Error> {
+        let req = self.build_request(Method::GET, VENDOR_CONFIG_PATH);
+        let res = self.send_request(req).await?;
+        Ok(res)
+    }
+}
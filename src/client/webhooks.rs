@@ -0,0 +1,386 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use reqwest::header::HeaderMap;
+use serde::de::{Error as _, Unexpected};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::Sha256;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::client::users::{WebhookUser, WebhookUserProfile};
+
+/// The envelope in which Frontegg delivers a webhook event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEnvelope {
+    /// The ID of the event.
+    ///
+    /// Useful for deduplicating events that are delivered more than once.
+    pub id: String,
+    /// The type of the event, e.g., `frontegg.user.created`.
+    pub event_key: String,
+    /// The context in which the event occurred.
+    pub event_context: WebhookEventContext,
+    /// The ID of the tenant for which the event occurred, if any.
+    pub tenant_id: Option<Uuid>,
+    /// The time at which the event was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The user that is the subject of a `frontegg.user.*` event.
+    pub user: Option<WebhookUser>,
+    /// The tenant that is the subject of a `frontegg.tenant.*` event.
+    pub tenant: Option<WebhookTenant>,
+}
+
+/// The context in which a webhook event occurred.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEventContext {
+    /// The ID of the vendor for which the event occurred.
+    pub vendor_id: Uuid,
+    /// The ID of the tenant for which the event occurred, if any.
+    pub tenant_id: Option<Uuid>,
+    /// The ID of the user that triggered the event, if any.
+    pub user_id: Option<Uuid>,
+}
+
+/// A tenant as it appears in a `frontegg.tenant.*` webhook event.
+///
+/// Unlike [`Tenant`], the timestamps other than the creation time may be
+/// absent, e.g., on `frontegg.tenant.created` events.
+///
+/// [`Tenant`]: crate::Tenant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTenant {
+    /// The ID of the tenant.
+    #[serde(rename = "tenantId")]
+    pub id: Uuid,
+    /// The name of the tenant.
+    pub name: String,
+    /// Arbitrary metadata that is attached to the tenant.
+    #[serde(default = "crate::serde::empty_json_object")]
+    #[serde(with = "crate::serde::nested_json")]
+    pub metadata: serde_json::Value,
+    /// The time at which the tenant was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The time at which the tenant was updated, if it has been updated.
+    #[serde(default, with = "crate::serde::timestamp::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    /// The time at which the tenant was deleted, if it has been deleted.
+    #[serde(default, with = "crate::serde::timestamp::option")]
+    pub deleted_at: Option<OffsetDateTime>,
+}
+
+/// A `frontegg.user.disabledMFA` webhook event.
+///
+/// Unlike a [`WebhookEnvelope`], which describes any event, exposes only the
+/// fields that Frontegg includes in this event. Deserializing an event of any
+/// other type fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisabledMfaEvent {
+    /// The ID of the event.
+    ///
+    /// Useful for deduplicating events that are delivered more than once.
+    pub id: String,
+    /// The type of the event, i.e., `frontegg.user.disabledMFA`.
+    #[serde(deserialize_with = "disabled_mfa_event_key")]
+    pub event_key: String,
+    /// The context in which the event occurred.
+    pub event_context: WebhookEventContext,
+    /// The ID of the tenant for which the event occurred, if any.
+    pub tenant_id: Option<Uuid>,
+    /// The time at which the event was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The user that disabled MFA.
+    ///
+    /// Unlike a [`WebhookUser`], omits the user's tenants, which are absent
+    /// from these events.
+    pub user: WebhookUserProfile,
+}
+
+/// A `frontegg.user.enrolledMFA` webhook event.
+///
+/// Unlike a [`WebhookEnvelope`], which describes any event, exposes only the
+/// fields that Frontegg includes in this event. Deserializing an event of any
+/// other type fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrolledMfaEvent {
+    /// The ID of the event.
+    ///
+    /// Useful for deduplicating events that are delivered more than once.
+    pub id: String,
+    /// The type of the event, i.e., `frontegg.user.enrolledMFA`.
+    #[serde(deserialize_with = "enrolled_mfa_event_key")]
+    pub event_key: String,
+    /// The context in which the event occurred.
+    pub event_context: WebhookEventContext,
+    /// The ID of the tenant for which the event occurred, if any.
+    pub tenant_id: Option<Uuid>,
+    /// The time at which the event was created.
+    #[serde(with = "crate::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// The user that enrolled in MFA.
+    pub user: EnrolledMfaUser,
+}
+
+/// A user as it appears in a `frontegg.user.enrolledMFA` webhook event.
+///
+/// Unlike [`WebhookUser`], the user's tenants are always present, but omit
+/// the user's roles in each tenant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrolledMfaUser {
+    /// The fields present in every `frontegg.user.*` webhook event.
+    #[serde(flatten)]
+    pub profile: WebhookUserProfile,
+    /// The IDs of all tenants for the user.
+    pub tenant_ids: Vec<Uuid>,
+    /// The tenants to which this user belongs.
+    pub tenants: Vec<EnrolledMfaTenantBinding>,
+}
+
+/// Binds an [`EnrolledMfaUser`] to a tenant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrolledMfaTenantBinding {
+    /// The ID of the tenant.
+    pub tenant_id: Uuid,
+}
+
+fn disabled_mfa_event_key<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    expect_event_key(deserializer, "frontegg.user.disabledMFA")
+}
+
+fn enrolled_mfa_event_key<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    expect_event_key(deserializer, "frontegg.user.enrolledMFA")
+}
+
+fn expect_event_key<'de, D>(deserializer: D, expected: &str) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let event_key = String::deserialize(deserializer)?;
+    if event_key != expected {
+        return Err(D::Error::invalid_value(
+            Unexpected::Str(&event_key),
+            &expected,
+        ));
+    }
+    Ok(event_key)
+}
+
+/// The default header in which Frontegg sends an HMAC webhook signature.
+const DEFAULT_HMAC_HEADER: &str = "x-webhook-signature";
+
+/// The default header in which Frontegg sends a JWT-signed webhook token.
+const DEFAULT_JWT_HEADER: &str = "x-webhook-secret";
+
+/// Verifies that a webhook request was sent by Frontegg.
+///
+/// Two signature formats are supported:
+///
+///   * An HMAC-SHA256 signature of the request body, hex encoded and
+///     optionally prefixed with `sha256=`. See [`WebhookVerifier::hmac`].
+///   * A JWT signed with either the webhook secret or a private key whose
+///     public key is configured on the verifier. See
+///     [`WebhookVerifier::jwt_secret`] and [`WebhookVerifier::jwt_public_key`].
+///
+/// Signatures are compared in constant time.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    header_name: String,
+    mode: WebhookVerifierMode,
+}
+
+#[derive(Clone)]
+enum WebhookVerifierMode {
+    Hmac {
+        secret: Vec<u8>,
+    },
+    Jwt {
+        key: DecodingKey,
+        validation: Box<Validation>,
+    },
+}
+
+impl fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match &self.mode {
+            WebhookVerifierMode::Hmac { .. } => "hmac",
+            WebhookVerifierMode::Jwt { .. } => "jwt",
+        };
+        f.debug_struct("WebhookVerifier")
+            .field("header_name", &self.header_name)
+            .field("mode", &mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookVerifier {
+    /// Creates a verifier for HMAC-SHA256 signatures of the request body
+    /// computed with the specified secret.
+    ///
+    /// The signature is read from the `x-webhook-signature` header by default.
+    pub fn hmac(secret: &str) -> WebhookVerifier {
+        WebhookVerifier {
+            header_name: DEFAULT_HMAC_HEADER.into(),
+            mode: WebhookVerifierMode::Hmac {
+                secret: secret.as_bytes().to_vec(),
+            },
+        }
+    }
+
+    /// Creates a verifier for JWTs signed with the specified secret via
+    /// HS256.
+    ///
+    /// The token is read from the `x-webhook-secret` header by default.
+    pub fn jwt_secret(secret: &str) -> WebhookVerifier {
+        WebhookVerifier::jwt(
+            DecodingKey::from_secret(secret.as_bytes()),
+            &[Algorithm::HS256],
+        )
+    }
+
+    /// Creates a verifier for JWTs signed with the private key corresponding
+    /// to the specified RSA or EC public key, in PEM format.
+    ///
+    /// The token is read from the `x-webhook-secret` header by default.
+    pub fn jwt_public_key(pem: &[u8]) -> Result<WebhookVerifier, WebhookVerifyError> {
+        if let Ok(key) = DecodingKey::from_rsa_pem(pem) {
+            let algorithms = [Algorithm::RS256, Algorithm::RS384, Algorithm::RS512];
+            Ok(WebhookVerifier::jwt(key, &algorithms))
+        } else if let Ok(key) = DecodingKey::from_ec_pem(pem) {
+            let algorithms = [Algorithm::ES256, Algorithm::ES384];
+            Ok(WebhookVerifier::jwt(key, &algorithms))
+        } else {
+            Err(WebhookVerifyError::InvalidKey)
+        }
+    }
+
+    fn jwt(key: DecodingKey, algorithms: &[Algorithm]) -> WebhookVerifier {
+        let mut validation = Validation::new(algorithms[0]);
+        validation.algorithms = algorithms.to_vec();
+        // Frontegg does not necessarily include an expiration time, but one
+        // is validated if present.
+        validation.required_spec_claims = HashSet::new();
+        WebhookVerifier {
+            header_name: DEFAULT_JWT_HEADER.into(),
+            mode: WebhookVerifierMode::Jwt {
+                key,
+                validation: Box::new(validation),
+            },
+        }
+    }
+
+    /// Sets the name of the header from which to read the signature.
+    pub fn with_header_name(mut self, header_name: &str) -> WebhookVerifier {
+        self.header_name = header_name.into();
+        self
+    }
+
+    /// Verifies the signature of a webhook request with the specified headers
+    /// and body.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookVerifyError> {
+        let signature = headers
+            .get(&*self.header_name)
+            .ok_or_else(|| WebhookVerifyError::MissingHeader(self.header_name.clone()))?
+            .to_str()
+            .map_err(|_| WebhookVerifyError::BadSignature)?
+            .trim();
+        match &self.mode {
+            WebhookVerifierMode::Hmac { secret } => {
+                let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+                let signature = decode_hex(signature).ok_or(WebhookVerifyError::BadSignature)?;
+                let mut mac =
+                    Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+                mac.update(body);
+                mac.verify_slice(&signature)
+                    .map_err(|_| WebhookVerifyError::BadSignature)
+            }
+            WebhookVerifierMode::Jwt { key, validation } => {
+                match jsonwebtoken::decode::<serde_json::Value>(signature, key, validation) {
+                    Ok(_) => Ok(()),
+                    Err(e) if *e.kind() == ErrorKind::ExpiredSignature => {
+                        Err(WebhookVerifyError::Expired)
+                    }
+                    Err(_) => Err(WebhookVerifyError::BadSignature),
+                }
+            }
+        }
+    }
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    // `u8::from_str_radix` accepts a leading sign, so check for strict hex
+    // digits up front.
+    if s.len() % 2 != 0 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// An error returned when verifying a webhook request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WebhookVerifyError {
+    /// The request did not include the named signature header.
+    MissingHeader(String),
+    /// The signature did not match the request.
+    BadSignature,
+    /// The signed token has expired.
+    Expired,
+    /// The configured public key could not be parsed.
+    InvalidKey,
+}
+
+impl fmt::Display for WebhookVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebhookVerifyError::MissingHeader(name) => {
+                write!(f, "webhook verification error: missing header {name}")
+            }
+            WebhookVerifyError::BadSignature => {
+                write!(f, "webhook verification error: bad signature")
+            }
+            WebhookVerifyError::Expired => write!(f, "webhook verification error: token expired"),
+            WebhookVerifyError::InvalidKey => {
+                write!(f, "webhook verification error: invalid public key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebhookVerifyError {}
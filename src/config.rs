@@ -13,14 +13,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::env::{self, VarError};
+use std::sync::Arc;
 use std::time::Duration;
 
 use once_cell::sync::Lazy;
 use reqwest::Url;
+use reqwest_middleware::Middleware;
 use reqwest_retry::policies::ExponentialBackoff;
 use reqwest_retry::RetryTransientMiddleware;
 
-use crate::client::Client;
+use crate::client::{Client, AUTH_VENDOR_PATH};
+use crate::error::{BuildError, EnvError};
+use crate::metrics::{FronteggMetrics, MetricsMiddleware};
+use crate::pool::Region;
+use crate::retry::{AttemptCounterMiddleware, UnjitteredBackoff};
+use crate::secret::SecretString;
+use crate::token::TokenStore;
+
+/// The environment variable that specifies the client ID.
+pub(crate) const CLIENT_ID_ENV_VAR: &str = "FRONTEGG_CLIENT_ID";
+/// The environment variable that specifies the secret key.
+pub(crate) const SECRET_KEY_ENV_VAR: &str = "FRONTEGG_SECRET_KEY";
+/// The environment variable that specifies the vendor endpoint.
+pub(crate) const ENDPOINT_ENV_VAR: &str = "FRONTEGG_ENDPOINT";
+/// The environment variable that specifies the region.
+pub(crate) const REGION_ENV_VAR: &str = "FRONTEGG_REGION";
 
 pub static DEFAULT_VENDOR_ENDPOINT: Lazy<Url> = Lazy::new(|| {
     "https://api.frontegg.com"
@@ -29,28 +47,83 @@ pub static DEFAULT_VENDOR_ENDPOINT: Lazy<Url> = Lazy::new(|| {
 });
 
 /// Configures the required parameters of a [`Client`].
+#[derive(Debug, Clone)]
 pub struct ClientConfig {
     /// The client ID for the vendor to authenticate as.
     pub client_id: String,
     /// The secret key for the vendor to authenticate as.
-    pub secret_key: String,
+    pub secret_key: SecretString,
+}
+
+impl ClientConfig {
+    /// Creates a new `ClientConfig` from a client ID and secret key.
+    pub fn new<I, S>(client_id: I, secret_key: S) -> ClientConfig
+    where
+        I: Into<String>,
+        S: Into<SecretString>,
+    {
+        ClientConfig {
+            client_id: client_id.into(),
+            secret_key: secret_key.into(),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Creates a new `ClientConfig` from the client ID and secret key in the
+    /// `FRONTEGG_CLIENT_ID` and `FRONTEGG_SECRET_KEY` environment variables.
+    ///
+    /// Returns an error naming the variable if either variable is not set or
+    /// is not valid Unicode.
+    pub fn from_env() -> Result<ClientConfig, EnvError> {
+        let client_id = var(CLIENT_ID_ENV_VAR)?;
+        let secret_key = var(SECRET_KEY_ENV_VAR)?;
+        match (client_id, secret_key) {
+            (Some(client_id), Some(secret_key)) => Ok(ClientConfig::new(client_id, secret_key)),
+            (None, _) => Err(EnvError::Missing(CLIENT_ID_ENV_VAR.into())),
+            (_, None) => Err(EnvError::Missing(SECRET_KEY_ENV_VAR.into())),
+        }
+    }
 }
 
 /// A builder for a [`Client`].
+#[derive(Clone)]
 pub struct ClientBuilder {
     vendor_endpoint: Url,
+    auth_path: Vec<String>,
     retry_policy: Option<ExponentialBackoff>,
+    jitter: bool,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    default_skip_invite_email: bool,
+    bulk_concurrency: usize,
+    max_error_body_len: usize,
+    max_response_size: usize,
+    middleware: Vec<Arc<dyn Middleware>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    metrics: Option<Arc<dyn FronteggMetrics>>,
 }
 
 impl Default for ClientBuilder {
     fn default() -> ClientBuilder {
         ClientBuilder {
             vendor_endpoint: DEFAULT_VENDOR_ENDPOINT.clone(),
+            auth_path: AUTH_VENDOR_PATH.iter().map(|s| s.to_string()).collect(),
             retry_policy: Some(
                 ExponentialBackoff::builder()
                     .retry_bounds(Duration::from_millis(100), Duration::from_secs(3))
                     .build_with_max_retries(5),
             ),
+            jitter: true,
+            timeout: Some(Duration::from_secs(60)),
+            connect_timeout: None,
+            default_skip_invite_email: false,
+            bulk_concurrency: 10,
+            max_error_body_len: 4096,
+            max_response_size: 16 << 20,
+            middleware: vec![],
+            token_store: None,
+            metrics: None,
         }
     }
 }
@@ -66,32 +139,310 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets whether to apply random jitter to the interval between retries.
+    ///
+    /// Jitter prevents many clients that fail at the same time from retrying
+    /// in lockstep. It is enabled by default. Disabling jitter makes the
+    /// interval between retries deterministic, which is primarily useful in
+    /// tests.
+    ///
+    /// The number of attempts made for a retried API call is reported in a
+    /// `DEBUG`-level [`tracing`] event.
+    ///
+    /// [`tracing`]: https://docs.rs/tracing
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the timeout for each HTTP request.
+    ///
+    /// The timeout applies from when the request starts connecting until the
+    /// response body has finished. The default timeout is 60 seconds.
+    ///
+    /// Passing `None` disables the timeout entirely. Without a timeout, a
+    /// request to an unresponsive server or over a dead connection may hang
+    /// indefinitely.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    ///
+    /// The connect timeout applies only to the connection phase of each HTTP
+    /// request, i.e., DNS resolution and TCP and TLS setup, and is independent
+    /// of the overall timeout configured via
+    /// [`with_timeout`](ClientBuilder::with_timeout). By default, there is no
+    /// connect timeout beyond the overall timeout.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether to skip sending an invitation email to users created via
+    /// [`create_user`](Client::create_user) by default.
+    ///
+    /// The default applies only to requests that leave
+    /// [`UserRequest::skip_invite_email`] unset. Defaults to `false`, i.e.,
+    /// invitation emails are sent.
+    ///
+    /// [`UserRequest::skip_invite_email`]: crate::UserRequest::skip_invite_email
+    pub fn with_default_skip_invite_email(mut self, skip: bool) -> Self {
+        self.default_skip_invite_email = skip;
+        self
+    }
+
+    /// Sets the maximum number of requests that bulk operations, like
+    /// [`create_tenants`](Client::create_tenants), have in flight at once.
+    ///
+    /// Defaults to 10. Higher limits speed up bulk operations but increase the
+    /// risk of exceeding Frontegg's rate limits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    pub fn with_bulk_concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "bulk concurrency must be positive");
+        self.bulk_concurrency = concurrency;
+        self
+    }
+
+    /// Sets the maximum number of bytes of an error response body to capture
+    /// in an [`Error::Api`] or in trace events.
+    ///
+    /// Defaults to 4KB. Longer bodies, and longer messages decoded from a
+    /// body, are truncated and marked with a trailing ellipsis.
+    ///
+    /// [`Error::Api`]: crate::Error::Api
+    pub fn with_max_error_body_len(mut self, len: usize) -> Self {
+        self.max_error_body_len = len;
+        self
+    }
+
+    /// Sets the maximum size of a response body, in bytes.
+    ///
+    /// A successful response whose body exceeds this size fails with
+    /// [`Error::ResponseTooLarge`] rather than being buffered in its
+    /// entirety. An error response whose body exceeds this size still fails
+    /// with an [`Error::Api`] that reports its status, but only the first
+    /// `size` bytes of its body are read. Defaults to 16MB.
+    ///
+    /// [`Error::Api`]: crate::Error::Api
+    /// [`Error::ResponseTooLarge`]: crate::Error::ResponseTooLarge
+    pub fn with_max_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = size;
+        self
+    }
+
+    /// Adds a middleware to the pipeline through which each HTTP request is
+    /// sent.
+    ///
+    /// May be called multiple times. Middleware is invoked in the order in
+    /// which it was added, with the first middleware added being outermost.
+    ///
+    /// All middleware added via this method runs inside the retry middleware,
+    /// so a retried API call invokes the middleware once per attempt.
+    /// Middleware also observes the requests the client makes to
+    /// authenticate with Frontegg.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Sets the store in which to persist authentication tokens.
+    ///
+    /// By default, tokens are cached only in memory, for the lifetime of the
+    /// [`Client`]. See [`TokenStore`] for details.
+    pub fn with_token_store<S>(mut self, token_store: S) -> Self
+    where
+        S: TokenStore,
+    {
+        self.token_store = Some(Arc::new(token_store));
+        self
+    }
+
+    /// Sets the callbacks through which to report metrics about each request.
+    ///
+    /// See [`FronteggMetrics`] for details.
+    pub fn with_metrics<M>(mut self, metrics: M) -> Self
+    where
+        M: FronteggMetrics,
+    {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
     /// Sets the vendor endpoint.
+    ///
+    /// The endpoint must be usable as a base URL. This is validated when the
+    /// client is built.
+    ///
+    /// Any path in the endpoint is preserved as a prefix of the path of each
+    /// API call. For example, if the endpoint is
+    /// `https://proxy.example.com/frontegg/`, users are fetched from
+    /// `https://proxy.example.com/frontegg/identity/resources/users/v1`.
     pub fn with_vendor_endpoint(mut self, endpoint: Url) -> Self {
         self.vendor_endpoint = endpoint;
         self
     }
 
+    /// Sets the path of the vendor authentication endpoint, relative to the
+    /// vendor endpoint.
+    ///
+    /// Defaults to `/auth/vendor`. Useful for self-hosted Frontegg
+    /// deployments that expose vendor authentication at a different path.
+    /// The authentication response may use either Frontegg's field names
+    /// (`token`, `expiresIn`) or OAuth-style field names (`access_token`,
+    /// `expires_in`).
+    pub fn with_auth_path(mut self, path: &str) -> Self {
+        self.auth_path = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+        self
+    }
+
+    /// Sets the vendor endpoint from the environment, if specified.
+    ///
+    /// The endpoint is read from the `FRONTEGG_ENDPOINT` environment variable
+    /// if set. Otherwise, the endpoint for the region named by the
+    /// `FRONTEGG_REGION` environment variable (one of `eu`, `us`, `ca`, or
+    /// `au`) is used if set. Otherwise, the vendor endpoint is left unchanged.
+    pub fn with_vendor_endpoint_from_env(self) -> Result<Self, EnvError> {
+        if let Some(endpoint) = var(ENDPOINT_ENV_VAR)? {
+            let endpoint: Url = endpoint.parse().map_err(|e| EnvError::Invalid {
+                name: ENDPOINT_ENV_VAR.into(),
+                message: format!("{e}"),
+            })?;
+            return Ok(self.with_vendor_endpoint(endpoint));
+        }
+        if let Some(region) = var(REGION_ENV_VAR)? {
+            let region = match region.to_ascii_lowercase().as_str() {
+                "eu" => Region::Eu,
+                "us" => Region::Us,
+                "ca" => Region::Ca,
+                "au" => Region::Au,
+                _ => {
+                    return Err(EnvError::Invalid {
+                        name: REGION_ENV_VAR.into(),
+                        message: format!("unknown region {region:?}"),
+                    })
+                }
+            };
+            return Ok(self.with_vendor_endpoint(region.vendor_endpoint()));
+        }
+        Ok(self)
+    }
+
     /// Creates a [`Client`] that incorporates the optional parameters
     /// configured on the builder and the specified required parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is invalid. See
+    /// [`ClientBuilder::try_build`] for a non-panicking variant.
     pub fn build(self, config: ClientConfig) -> Client {
-        let client = reqwest::ClientBuilder::new()
-            .redirect(reqwest::redirect::Policy::none())
-            .timeout(Duration::from_secs(60))
-            .build()
-            .unwrap();
+        self.try_build(config).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`ClientBuilder::build`], but returns an error rather than
+    /// panicking if the configuration is invalid.
+    ///
+    /// The configuration is invalid if the vendor endpoint cannot be used as a
+    /// base URL.
+    pub fn try_build(self, config: ClientConfig) -> Result<Client, BuildError> {
+        self.validate()?;
+        let client = self.build_http_client();
+        Ok(self.build_with_http_client(client, config))
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), BuildError> {
+        if self.vendor_endpoint.cannot_be_a_base() {
+            return Err(BuildError::InvalidVendorEndpoint(
+                self.vendor_endpoint.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Creates the underlying HTTP client.
+    ///
+    /// The HTTP client maintains a connection pool. Sharing it between
+    /// [`Client`]s via [`ClientBuilder::build_with_http_client`] allows those
+    /// clients to reuse connections.
+    pub(crate) fn build_http_client(&self) -> reqwest::Client {
+        let mut builder = reqwest::ClientBuilder::new().redirect(reqwest::redirect::Policy::none());
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        builder.build().unwrap()
+    }
+
+    /// Like [`ClientBuilder::build`], but uses the specified HTTP client,
+    /// which must have been created by [`ClientBuilder::build_http_client`].
+    pub(crate) fn build_with_http_client(
+        self,
+        client: reqwest::Client,
+        config: ClientConfig,
+    ) -> Client {
+        if let Err(e) = self.validate() {
+            panic!("{e}");
+        }
+        let base = || {
+            let builder = reqwest_middleware::ClientBuilder::new(client.clone());
+            match &self.metrics {
+                Some(metrics) => builder.with(MetricsMiddleware(Arc::clone(metrics))),
+                None => builder,
+            }
+        };
+        let with_middleware = |mut builder: reqwest_middleware::ClientBuilder| {
+            for middleware in &self.middleware {
+                builder = builder.with_arc(Arc::clone(middleware));
+            }
+            builder.build()
+        };
         Client {
-            client_retryable: match self.retry_policy {
-                Some(policy) => reqwest_middleware::ClientBuilder::new(client.clone())
-                    .with(RetryTransientMiddleware::new_with_policy(policy))
-                    .build(),
-                None => reqwest_middleware::ClientBuilder::new(client.clone()).build(),
-            },
-            client_non_retryable: reqwest_middleware::ClientBuilder::new(client).build(),
+            client_retryable: with_middleware(match &self.retry_policy {
+                Some(policy) if self.jitter => base()
+                    .with(RetryTransientMiddleware::new_with_policy(*policy))
+                    .with(AttemptCounterMiddleware),
+                Some(policy) => base()
+                    .with(RetryTransientMiddleware::new_with_policy(
+                        UnjitteredBackoff(*policy),
+                    ))
+                    .with(AttemptCounterMiddleware),
+                None => base(),
+            }),
+            client_non_retryable: with_middleware(base()),
             client_id: config.client_id,
             secret_key: config.secret_key,
             vendor_endpoint: self.vendor_endpoint,
+            auth_path: self.auth_path,
+            default_skip_invite_email: self.default_skip_invite_email,
+            bulk_concurrency: self.bulk_concurrency,
+            max_error_body_len: self.max_error_body_len,
+            max_response_size: self.max_response_size,
+            token_store: self.token_store.clone(),
             auth: Default::default(),
+            auth_refresh: Default::default(),
+            last_rate_limit: Default::default(),
         }
     }
 }
+
+/// Reads an environment variable, treating an unset variable as `None`.
+fn var(name: &str) -> Result<Option<String>, EnvError> {
+    match env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(_)) => Err(EnvError::Invalid {
+            name: name.into(),
+            message: "not valid unicode".into(),
+        }),
+    }
+}
@@ -14,18 +14,98 @@
 // limitations under the License.
 
 use std::fmt;
+use std::time::Duration;
 
-use reqwest::StatusCode;
+use reqwest::{StatusCode, Url};
+
+use crate::id::TenantId;
 
 /// An error returned by a [`Client`].
 ///
 /// [`Client`]: crate::Client
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// An error in the underlying transport.
     Transport(reqwest_middleware::Error),
     /// An error returned by the API.
     Api(ApiError),
+    /// An error decoding a successful API response.
+    Decode(serde_json::Error),
+    /// A read-only API call failed with a transient error on every attempt
+    /// permitted by the retry policy.
+    RetriesExhausted {
+        /// The number of attempts made.
+        attempts: u32,
+        /// The error from the last attempt.
+        last: Box<Error>,
+    },
+    /// A successful response body exceeded the maximum size configured via
+    /// [`ClientBuilder::with_max_response_size`].
+    ///
+    /// [`ClientBuilder::with_max_response_size`]: crate::ClientBuilder::with_max_response_size
+    ResponseTooLarge {
+        /// The maximum size, in bytes.
+        limit: usize,
+    },
+    /// An operation did not complete within its overall timeout, e.g., the
+    /// timeout configured via [`UserListConfig::total_timeout`].
+    ///
+    /// [`UserListConfig::total_timeout`]: crate::UserListConfig::total_timeout
+    TimedOut {
+        /// The timeout that was exceeded.
+        timeout: Duration,
+    },
+    /// A tenant was not deleted via [`Client::delete_tenant_with_options`]
+    /// without cascading because it still has users.
+    ///
+    /// This error is raised by the client, not by Frontegg.
+    ///
+    /// [`Client::delete_tenant_with_options`]: crate::Client::delete_tenant_with_options
+    TenantHasUsers {
+        /// The ID of the tenant.
+        tenant_id: TenantId,
+        /// The number of users the tenant had.
+        users: u64,
+    },
+    /// A tenant metadata key cannot be addressed individually, as with the
+    /// keys rejected by [`Client::delete_tenant_metadata`].
+    ///
+    /// This error is raised by the client, not by Frontegg.
+    ///
+    /// [`Client::delete_tenant_metadata`]: crate::Client::delete_tenant_metadata
+    InvalidMetadataKey {
+        /// The rejected key.
+        key: String,
+    },
+}
+
+impl Error {
+    /// Constructs an [`Error::Api`] with the specified status code and
+    /// messages.
+    ///
+    /// Useful for fabricating errors in test doubles.
+    pub fn api(status_code: StatusCode, messages: Vec<String>) -> Error {
+        Error::Api(ApiError::new(status_code, messages))
+    }
+
+    /// Returns the HTTP status code of an error returned by the API.
+    ///
+    /// For an [`Error::RetriesExhausted`], returns the status code of the
+    /// error from the last attempt. Returns `None` for errors that did not
+    /// originate from an API response, like transport and decode errors.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            Error::Api(e) => Some(e.status_code),
+            Error::RetriesExhausted { last, .. } => last.status_code(),
+            Error::Transport(_)
+            | Error::Decode(_)
+            | Error::ResponseTooLarge { .. }
+            | Error::TimedOut { .. }
+            | Error::TenantHasUsers { .. }
+            | Error::InvalidMetadataKey { .. } => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -33,14 +113,129 @@ impl fmt::Display for Error {
         match self {
             Error::Transport(e) => write!(f, "frontegg error: transport: {e}"),
             Error::Api(e) => write!(f, "frontegg error: api: {e}"),
+            Error::Decode(e) => write!(f, "frontegg error: decode: {e}"),
+            Error::RetriesExhausted { attempts, last } => {
+                write!(f, "{last} (after {attempts} attempts)")
+            }
+            Error::ResponseTooLarge { limit } => {
+                write!(f, "frontegg error: response body exceeds {limit} bytes")
+            }
+            Error::TimedOut { timeout } => {
+                write!(f, "frontegg error: operation timed out after {timeout:?}")
+            }
+            Error::TenantHasUsers { tenant_id, users } => write!(
+                f,
+                "frontegg error: tenant {tenant_id} still has {users} users; \
+                 remove them or delete with cascade"
+            ),
+            Error::InvalidMetadataKey { key } => {
+                write!(f, "frontegg error: invalid tenant metadata key {key:?}")
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
 
+/// An error returned when building a [`Client`] from an invalid
+/// configuration.
+///
+/// [`Client`]: crate::Client
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The vendor endpoint cannot be used as a base URL, e.g., because it is
+    /// a `mailto:` or `data:` URL.
+    InvalidVendorEndpoint(Url),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::InvalidVendorEndpoint(url) => write!(
+                f,
+                "frontegg build error: vendor endpoint {url} cannot be a base URL"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// An error returned when configuring a [`Client`] from environment
+/// variables.
+///
+/// [`Client`]: crate::Client
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum EnvError {
+    /// A required environment variable is not set.
+    Missing(String),
+    /// An environment variable is set to an invalid value.
+    Invalid {
+        /// The name of the environment variable.
+        name: String,
+        /// A description of why the value is invalid.
+        message: String,
+    },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EnvError::Missing(name) => {
+                write!(f, "frontegg env error: {name} is not set")
+            }
+            EnvError::Invalid { name, message } => {
+                write!(f, "frontegg env error: {name} is invalid: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+/// An error returned when parsing a [`TenantId`] or [`UserId`] from a
+/// string.
+///
+/// [`TenantId`]: crate::TenantId
+/// [`UserId`]: crate::UserId
+#[derive(Debug, Clone)]
+pub struct ParseIdError {
+    kind: &'static str,
+    input: String,
+    source: uuid::Error,
+}
+
+impl ParseIdError {
+    pub(crate) fn new(kind: &'static str, input: &str, source: uuid::Error) -> ParseIdError {
+        ParseIdError {
+            kind,
+            input: input.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid {} ID {:?}: {}",
+            self.kind, self.input, self.source
+        )
+    }
+}
+
+impl std::error::Error for ParseIdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
 /// An error returned by the Frontegg API.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ApiError {
     /// The HTTP status code.
     pub status_code: StatusCode,
@@ -48,6 +243,16 @@ pub struct ApiError {
     pub messages: Vec<String>,
 }
 
+impl ApiError {
+    /// Constructs a new API error.
+    pub fn new(status_code: StatusCode, messages: Vec<String>) -> ApiError {
+        ApiError {
+            status_code,
+            messages,
+        }
+    }
+}
+
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -73,6 +278,12 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error {
+        Error::Decode(e)
+    }
+}
+
 impl From<ApiError> for Error {
     fn from(e: ApiError) -> Error {
         Error::Api(e)
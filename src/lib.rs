@@ -34,18 +34,47 @@
 //!
 //! [official-api-docs]: https://docs.frontegg.com/reference/getting-started-with-your-api
 
+mod api;
 #[warn(missing_debug_implementations, missing_docs)]
 mod client;
 mod config;
 mod error;
+mod id;
+mod metrics;
+mod pool;
+mod retry;
+mod secret;
 mod serde;
+mod token;
 mod util;
 
-pub use client::roles::{Permission, Role};
-pub use client::tenants::{Tenant, TenantRequest};
+pub use api::FronteggApi;
+pub use client::api_tokens::{ApiToken, ApiTokenRequest};
+pub use client::applications::{Application, ApplicationRequest};
+pub use client::audits::{AuditLogConfig, AuditLogEntry};
+pub use client::domains::TenantDomain;
+pub use client::roles::{
+    Permission, PermissionCategory, PermissionListConfig, Role, RoleListConfig,
+};
+pub use client::scoped::TenantScopedClient;
+pub use client::sso::{SsoConfig, SsoConfigRequest};
+pub use client::tenants::{
+    Tenant, TenantListConfig, TenantRequest, TenantRequestBuilder, TenantSortBy, TenantStatus,
+};
 pub use client::users::{
-    CreatedUser, User, UserListConfig, UserRequest, WebhookTenantBinding, WebhookUser,
+    CreatedUser, ImpersonationToken, OwnedUserRequest, User, UserListConfig, UserRequest,
+    UserRequestBuilder, WebhookTenantBinding, WebhookUser, WebhookUserProfile,
+};
+pub use client::vendors::VendorConfig;
+pub use client::webhooks::{
+    DisabledMfaEvent, EnrolledMfaEvent, EnrolledMfaTenantBinding, EnrolledMfaUser, WebhookEnvelope,
+    WebhookEventContext, WebhookTenant, WebhookVerifier, WebhookVerifyError,
 };
-pub use client::Client;
+pub use client::{AsyncOperation, Client, RateLimit, SortOrder};
 pub use config::{ClientBuilder, ClientConfig};
-pub use error::{ApiError, Error};
+pub use error::{ApiError, BuildError, EnvError, Error, ParseIdError};
+pub use id::{TenantId, UserId};
+pub use metrics::FronteggMetrics;
+pub use pool::{ClientPool, Region};
+pub use secret::SecretString;
+pub use token::{StoredToken, TokenKey, TokenStore};
@@ -0,0 +1,78 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reqwest::{Method, Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use task_local_extensions::Extensions;
+
+/// Callbacks for recording metrics about the requests a [`Client`] makes.
+///
+/// Configure via [`ClientBuilder::with_metrics`]. Implementations typically
+/// forward the observations to a metrics library, e.g., to maintain a
+/// Prometheus request counter and latency histogram.
+///
+/// Callbacks are invoked synchronously on the request path, so they should
+/// not block.
+///
+/// [`Client`]: crate::Client
+/// [`ClientBuilder::with_metrics`]: crate::ClientBuilder::with_metrics
+pub trait FronteggMetrics: Send + Sync + 'static {
+    /// Called when a request to Frontegg completes.
+    ///
+    /// The `path` is the path of the request URL, including the path of the
+    /// vendor endpoint and any IDs embedded in the path. Implementations that
+    /// use the path as a metric label may wish to normalize it to avoid
+    /// unbounded cardinality.
+    ///
+    /// The `status` is `None` if no response was received, e.g., due to a
+    /// connection error or timeout. The `duration` covers the entire call,
+    /// including any retries, up until the response headers are received.
+    fn on_request_complete(
+        &self,
+        path: &str,
+        method: &Method,
+        status: Option<StatusCode>,
+        duration: Duration,
+    );
+}
+
+/// Reports each request to a [`FronteggMetrics`] implementation.
+///
+/// Must be installed above the retry middleware so that it observes each
+/// call, rather than each individual attempt.
+pub(crate) struct MetricsMiddleware(pub(crate) Arc<dyn FronteggMetrics>);
+
+#[async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let path = req.url().path().to_owned();
+        let method = req.method().clone();
+        let start = Instant::now();
+        let res = next.run(req, extensions).await;
+        let status = res.as_ref().ok().map(|res| res.status());
+        self.0
+            .on_request_complete(&path, &method, status, start.elapsed());
+        res
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use reqwest::Url;
+
+use crate::config::DEFAULT_VENDOR_ENDPOINT;
+use crate::error::BuildError;
+use crate::{Client, ClientBuilder, ClientConfig};
+
+/// A Frontegg region.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// The EU region.
+    Eu,
+    /// The US region.
+    Us,
+    /// The CA region.
+    Ca,
+    /// The AU region.
+    Au,
+    /// A region served by a custom vendor endpoint.
+    Custom(Url),
+}
+
+impl Region {
+    /// Returns the vendor endpoint for the region.
+    pub fn vendor_endpoint(&self) -> Url {
+        let endpoint = match self {
+            Region::Eu => return DEFAULT_VENDOR_ENDPOINT.clone(),
+            Region::Us => "https://api.us.frontegg.com",
+            Region::Ca => "https://api.ca.frontegg.com",
+            Region::Au => "https://api.au.frontegg.com",
+            Region::Custom(url) => return url.clone(),
+        };
+        endpoint.parse().expect("url known to be valid")
+    }
+}
+
+/// A pool of [`Client`]s for multiple Frontegg regions.
+///
+/// Each region is configured with its own credentials. The client for a region
+/// is created on first use and cached thereafter. Each client maintains its
+/// own authentication state, but all clients share the same underlying HTTP
+/// connection pool.
+///
+/// Like [`Client`], the pool is designed to be wrapped in an [`Arc`] and used
+/// from multiple threads simultaneously.
+pub struct ClientPool {
+    builder: ClientBuilder,
+    http_client: reqwest::Client,
+    configs: HashMap<Region, ClientConfig>,
+    clients: Mutex<HashMap<Region, Arc<Client>>>,
+}
+
+impl fmt::Debug for ClientPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientPool")
+            .field("regions", &self.configs.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientPool {
+    /// Creates a new pool whose clients are configured by `builder`.
+    ///
+    /// The vendor endpoint configured on `builder` is ignored in favor of the
+    /// endpoint for each region.
+    pub fn new(builder: ClientBuilder) -> ClientPool {
+        ClientPool {
+            http_client: builder.build_http_client(),
+            builder,
+            configs: HashMap::new(),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configures the credentials to use for the specified region.
+    ///
+    /// Returns an error if the region's vendor endpoint cannot be used as a
+    /// base URL, as for [`ClientBuilder::try_build`].
+    pub fn with_region(mut self, region: Region, config: ClientConfig) -> Result<Self, BuildError> {
+        self.builder
+            .clone()
+            .with_vendor_endpoint(region.vendor_endpoint())
+            .validate()?;
+        self.configs.insert(region, config);
+        Ok(self)
+    }
+
+    /// Returns the client for the specified region.
+    ///
+    /// Returns `None` if the region has not been configured via
+    /// [`ClientPool::with_region`].
+    pub fn client(&self, region: &Region) -> Option<Arc<Client>> {
+        let config = self.configs.get(region)?;
+        let mut clients = self.clients.lock().expect("lock poisoned");
+        let client = clients.entry(region.clone()).or_insert_with(|| {
+            let client = self
+                .builder
+                .clone()
+                .with_vendor_endpoint(region.vendor_endpoint())
+                .build_with_http_client(self.http_client.clone(), config.clone());
+            Arc::new(client)
+        });
+        Some(Arc::clone(client))
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use reqwest_retry::policies::ExponentialBackoff;
+use reqwest_retry::RetryPolicy;
+use retry_policies::RetryDecision;
+use task_local_extensions::Extensions;
+
+/// An [`ExponentialBackoff`] policy that waits exactly the computed backoff
+/// interval between attempts.
+///
+/// The [`ExponentialBackoff`] policy always applies jitter. This wrapper
+/// computes the same intervals without it.
+#[derive(Debug, Clone, Copy)]
+pub struct UnjitteredBackoff(pub ExponentialBackoff);
+
+impl RetryPolicy for UnjitteredBackoff {
+    fn should_retry(&self, n_past_retries: u32) -> RetryDecision {
+        let policy = &self.0;
+        if n_past_retries >= policy.max_n_retries {
+            return RetryDecision::DoNotRetry;
+        }
+        let factor = policy
+            .backoff_exponent
+            .checked_pow(n_past_retries)
+            .unwrap_or(u32::MAX);
+        let wait = cmp::min(
+            policy.min_retry_interval.saturating_mul(factor),
+            policy.max_retry_interval,
+        );
+        // The maximum retry interval is user specified, so saturate rather
+        // than overflow if it is very large.
+        let execute_after = chrono::Duration::from_std(wait)
+            .ok()
+            .and_then(|wait| chrono::Utc::now().checked_add_signed(wait))
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC);
+        RetryDecision::Retry { execute_after }
+    }
+}
+
+/// Counts the number of attempts made for a request.
+///
+/// Attach to a request as an extension. The [`AttemptCounterMiddleware`]
+/// increments the counter once per attempt.
+#[derive(Debug, Clone, Default)]
+pub struct AttemptCounter(Arc<AtomicU32>);
+
+impl AttemptCounter {
+    /// Returns the number of attempts made so far.
+    pub fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Increments the [`AttemptCounter`] attached to each request, if any.
+///
+/// Must be installed beneath the retry middleware so that it observes each
+/// individual attempt.
+#[derive(Debug)]
+pub struct AttemptCounterMiddleware;
+
+#[async_trait]
+impl Middleware for AttemptCounterMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if let Some(counter) = extensions.get::<AttemptCounter>() {
+            counter.0.fetch_add(1, Ordering::Relaxed);
+        }
+        next.run(req, extensions).await
+    }
+}
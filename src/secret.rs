@@ -0,0 +1,71 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A string containing a secret, like a secret key or an authentication
+/// token.
+///
+/// The `Debug` and `Display` implementations redact the secret, so that it is
+/// not accidentally written to logs. The secret itself must be retrieved
+/// explicitly via [`SecretString::expose`]. Serialization, by contrast,
+/// includes the secret, so that structs containing secrets can be persisted,
+/// as by a [`TokenStore`].
+///
+/// [`TokenStore`]: crate::TokenStore
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Creates a new `SecretString`.
+    pub fn new<S>(secret: S) -> SecretString
+    where
+        S: Into<String>,
+    {
+        SecretString(secret.into())
+    }
+
+    /// Returns the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> SecretString {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> SecretString {
+        SecretString(secret.into())
+    }
+}
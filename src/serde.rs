@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Deserializer};
 
 /// The pagination wrapper type for API calls that are paginated.
@@ -27,62 +28,266 @@ pub struct Paginated<T> {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginatedMetadata {
+    /// The total number of items, which not every paginated endpoint reports.
+    #[serde(default, deserialize_with = "lenient::deserialize_option")]
+    pub total_items: Option<u64>,
+    #[serde(deserialize_with = "lenient::deserialize")]
     pub total_pages: u64,
 }
 
-/// A struct that deserializes nothing.
+/// A struct that deserializes from anything, discarding its contents.
 ///
-/// Useful for deserializing empty response bodies.
+/// Useful for deserializing response bodies that are empty or uninteresting.
 pub struct Empty;
 
 impl<'de> Deserialize<'de> for Empty {
-    fn deserialize<D>(_: D) -> Result<Empty, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Empty, D::Error>
     where
         D: Deserializer<'de>,
     {
+        IgnoredAny::deserialize(deserializer)?;
         Ok(Empty)
     }
 }
 
+/// Serialization for JSON values that Frontegg nests inside a JSON string.
+///
+/// Deserialization accepts either the nested form or, as produced when
+/// reserializing a previously deserialized value, the value itself.
+/// Serialization emits the value itself, except that a string is nested, as
+/// a bare string would otherwise be read back as nested JSON.
 pub mod nested_json {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Value::String(_) => value.to_string().serialize(serializer),
+            _ => value.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => serde_json::from_str(&s).map_err(D::Error::custom),
+            value => Ok(value),
+        }
+    }
+}
+
+/// Deserialization that tolerates scalars of the wrong type.
+///
+/// Frontegg occasionally reports numbers and booleans as strings, and vice
+/// versa. A string is accepted if it parses as the expected type, and a number
+/// or boolean is accepted if its string representation does.
+pub mod lenient {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use serde::de::{DeserializeOwned, Error};
+    use serde::{Deserialize, Deserializer};
+    use serde_json::Value;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: DeserializeOwned + FromStr,
+        T::Err: Display,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => s.trim().parse().map_err(D::Error::custom),
+            value @ (Value::Number(_) | Value::Bool(_)) => T::deserialize(&value)
+                .or_else(|e| value.to_string().parse().map_err(|_| e))
+                .map_err(D::Error::custom),
+            value => T::deserialize(value).map_err(D::Error::custom),
+        }
+    }
+
+    /// Like [`deserialize`], but treats `null` as `None`.
+    pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: DeserializeOwned + FromStr,
+        T::Err: Display,
+    {
+        match Option::<Value>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(value) => deserialize(value).map(Some).map_err(D::Error::custom),
+        }
+    }
+}
+
+pub fn empty_json_object() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
+}
+
+/// Serialization for timestamps that tolerates the RFC 3339 variants emitted by
+/// the Frontegg API.
+///
+/// Timestamps are serialized in a canonical RFC 3339 format: in UTC, with a
+/// `Z` suffix, and with millisecond precision unless additional precision is
+/// required to represent the timestamp exactly. This matches the format used
+/// by Frontegg, so a timestamp that was deserialized from Frontegg reserializes
+/// to the same string.
+///
+/// During deserialization, timestamps in RFC 3339 format are accepted with or
+/// without fractional seconds and with either a `Z` or numeric offset.
+/// Timestamps that are missing an offset entirely, or that use a space rather
+/// than a `T` to separate the date and time, are also accepted and assumed to
+/// be in UTC.
+pub mod timestamp {
     use std::fmt;
 
-    use serde::de::{Error, Visitor};
-    use serde::Deserializer;
+    use serde::de::Visitor;
+    use serde::ser::Error as _;
+    use serde::{de, Deserializer, Serializer};
+    use time::format_description::well_known::Rfc3339;
+    use time::{OffsetDateTime, UtcOffset};
+
+    fn parse(s: &str) -> Option<OffsetDateTime> {
+        if let Ok(t) = OffsetDateTime::parse(s, &Rfc3339) {
+            return Some(t);
+        }
+        // Normalize the variants that are not strictly RFC 3339 and try again.
+        let mut s = s.replacen(' ', "T", 1);
+        let (_, time) = s.split_once('T')?;
+        if !time.ends_with(['Z', 'z']) && !time.contains(['+', '-']) {
+            s.push('Z');
+        }
+        OffsetDateTime::parse(&s, &Rfc3339).ok()
+    }
+
+    struct TimestampVisitor;
+
+    impl<'de> Visitor<'de> for TimestampVisitor {
+        type Value = OffsetDateTime;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "an RFC 3339 timestamp")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<OffsetDateTime, E>
+        where
+            E: de::Error,
+        {
+            parse(value).ok_or_else(|| E::custom(format!("invalid timestamp: {value}")))
+        }
+    }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<serde_json::Value, D::Error>
+    fn format(t: OffsetDateTime) -> Option<String> {
+        let t = t.to_offset(UtcOffset::UTC);
+        if !(0..=9999).contains(&t.year()) {
+            return None;
+        }
+        let nanos = t.nanosecond();
+        let fraction = if nanos % 1_000_000 == 0 {
+            format!("{:03}", nanos / 1_000_000)
+        } else if nanos % 1_000 == 0 {
+            format!("{:06}", nanos / 1_000)
+        } else {
+            format!("{nanos:09}")
+        };
+        Some(format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{fraction}Z",
+            t.year(),
+            u8::from(t.month()),
+            t.day(),
+            t.hour(),
+            t.minute(),
+            t.second(),
+        ))
+    }
+
+    pub fn serialize<S>(t: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match format(*t) {
+            Some(t) => serializer.serialize_str(&t),
+            None => Err(S::Error::custom(format!(
+                "timestamp out of range for RFC 3339: {t}"
+            ))),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
     where
         D: Deserializer<'de>,
     {
-        #[derive(Default)]
-        struct NestedJson;
+        deserializer.deserialize_str(TimestampVisitor)
+    }
 
-        impl<'de> Visitor<'de> for NestedJson {
-            type Value = serde_json::Value;
+    pub mod option {
+        use std::fmt;
+
+        use serde::de::{Error, Visitor};
+        use serde::{Deserializer, Serialize, Serializer};
+        use time::OffsetDateTime;
+
+        struct OptionVisitor;
+
+        impl<'de> Visitor<'de> for OptionVisitor {
+            type Value = Option<OffsetDateTime>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(formatter, "valid nested json object")
+                write!(formatter, "an RFC 3339 timestamp or null")
             }
 
-            fn visit_unit<E>(self) -> Result<serde_json::Value, E>
+            fn visit_none<E>(self) -> Result<Option<OffsetDateTime>, E>
             where
                 E: Error,
             {
-                Ok(serde_json::Value::Null)
+                Ok(None)
             }
 
-            fn visit_str<E>(self, value: &str) -> Result<serde_json::Value, E>
+            fn visit_unit<E>(self) -> Result<Option<OffsetDateTime>, E>
             where
                 E: Error,
             {
-                serde_json::from_str(value).map_err(Error::custom)
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Option<OffsetDateTime>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                super::deserialize(deserializer).map(Some)
             }
         }
 
-        deserializer.deserialize_any(NestedJson)
-    }
-}
+        pub fn serialize<S>(t: &Option<OffsetDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            struct Timestamp<'a>(&'a OffsetDateTime);
 
-pub fn empty_json_object() -> serde_json::Value {
-    serde_json::Value::Object(serde_json::Map::new())
+            impl Serialize for Timestamp<'_> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    super::serialize(self.0, serializer)
+                }
+            }
+
+            match t {
+                Some(t) => serializer.serialize_some(&Timestamp(t)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<OffsetDateTime>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_option(OptionVisitor)
+        }
+    }
 }
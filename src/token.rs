@@ -0,0 +1,98 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::secret::SecretString;
+
+/// A vendor authentication token, as persisted by a [`TokenStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredToken {
+    /// The token.
+    pub token: SecretString,
+    /// The time after which the token should be replaced by a new one.
+    ///
+    /// A loaded token is used only until this time, even though it remains
+    /// valid until it expires, so that clients sharing the token do not use
+    /// it right up to its expiry.
+    #[serde(with = "crate::serde::timestamp")]
+    pub refresh_at: OffsetDateTime,
+    /// The time at which the token expires.
+    #[serde(with = "crate::serde::timestamp")]
+    pub expires_at: OffsetDateTime,
+}
+
+/// The credentials to which a [`StoredToken`] was issued.
+///
+/// The [`Display`](fmt::Display) representation is suitable for use as the
+/// key of a string-keyed store, like Redis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TokenKey {
+    /// The vendor endpoint that issued the token.
+    pub vendor_endpoint: Url,
+    /// The client ID to which the token was issued.
+    pub client_id: String,
+}
+
+impl fmt::Display for TokenKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.client_id, self.vendor_endpoint)
+    }
+}
+
+/// A persistent store for vendor authentication tokens.
+///
+/// A [`Client`] caches its authentication token in memory. Configuring a
+/// token store via [`ClientBuilder::with_token_store`] additionally allows the
+/// token to be reused across clients and process restarts, e.g., by
+/// persisting it to Redis or to disk, which avoids reauthenticating on each
+/// cold start.
+///
+/// Tokens are keyed by the vendor endpoint and client ID they were issued to,
+/// so one store may be shared by clients with different credentials or in
+/// different regions.
+///
+/// Errors returned by a token store are logged and otherwise ignored: a
+/// failure to load a token causes the client to authenticate, and a failure to
+/// store a token does not prevent its use.
+///
+/// [`Client`]: crate::Client
+/// [`ClientBuilder::with_token_store`]: crate::ClientBuilder::with_token_store
+#[async_trait]
+pub trait TokenStore: Send + Sync + 'static {
+    /// Loads the token for the specified key, if one is stored.
+    ///
+    /// The returned token may be due for a refresh or have expired, in which
+    /// case it is ignored.
+    async fn load(
+        &self,
+        key: &TokenKey,
+    ) -> Result<Option<StoredToken>, Box<dyn StdError + Send + Sync>>;
+
+    /// Stores the token for the specified key, replacing any token previously
+    /// stored for the key.
+    async fn store(
+        &self,
+        key: &TokenKey,
+        token: &StoredToken,
+    ) -> Result<(), Box<dyn StdError + Send + Sync>>;
+}
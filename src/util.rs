@@ -13,20 +13,53 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::fmt;
 use std::iter;
 
+use chrono::{TimeZone, Utc};
 use reqwest_middleware::RequestBuilder;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::id::TenantId;
+
 pub trait RequestBuilderExt {
-    fn tenant(self, uuid: Uuid) -> RequestBuilder;
+    fn tenant(self, id: impl Into<TenantId>) -> RequestBuilder;
+    fn application(self, uuid: Uuid) -> RequestBuilder;
 }
 
 impl RequestBuilderExt for RequestBuilder {
-    fn tenant(self, uuid: Uuid) -> RequestBuilder {
-        self.header("Frontegg-Tenant-Id", uuid.to_string())
+    fn tenant(self, id: impl Into<TenantId>) -> RequestBuilder {
+        self.header("Frontegg-Tenant-Id", id.into().to_string())
+    }
+
+    fn application(self, uuid: Uuid) -> RequestBuilder {
+        self.header("Frontegg-Application-Id", uuid.to_string())
+    }
+}
+
+/// Converts a [`time`] timestamp to the equivalent [`chrono`] timestamp.
+pub fn to_chrono(t: OffsetDateTime) -> chrono::DateTime<Utc> {
+    Utc.timestamp_opt(t.unix_timestamp(), t.nanosecond())
+        .single()
+        .expect("time range within chrono range")
+}
+
+/// Truncates `s` to at most `max_len` bytes, appending an ellipsis if any
+/// bytes are removed.
+///
+/// Truncation respects character boundaries, so the result may be slightly
+/// shorter than `max_len` bytes, not counting the ellipsis.
+pub fn truncate(s: &str, max_len: usize) -> Cow<'_, str> {
+    if s.len() <= max_len {
+        return Cow::Borrowed(s);
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
     }
+    Cow::Owned(format!("{}…", &s[..end]))
 }
 
 pub trait StrIteratorExt {
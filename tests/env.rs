@@ -0,0 +1,115 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for configuring a client from environment variables.
+//!
+//! These tests live in their own test binary, as modifying the environment
+//! would race with other tests that read it.
+
+use std::env;
+
+use serde_json::json;
+use test_log::test;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+use frontegg::{Client, ClientConfig, EnvError};
+
+/// Tests configuring a client from environment variables.
+#[test(tokio::test)]
+async fn test_from_env() {
+    /// Restores the environment variables it guards when dropped.
+    struct EnvGuard(Vec<(&'static str, Option<std::ffi::OsString>)>);
+
+    impl EnvGuard {
+        fn new(names: &[&'static str]) -> EnvGuard {
+            EnvGuard(names.iter().map(|n| (*n, env::var_os(n))).collect())
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (name, value) in &self.0 {
+                match value {
+                    Some(value) => env::set_var(name, value),
+                    None => env::remove_var(name),
+                }
+            }
+        }
+    }
+
+    let _guard = EnvGuard::new(&[
+        "FRONTEGG_CLIENT_ID",
+        "FRONTEGG_SECRET_KEY",
+        "FRONTEGG_ENDPOINT",
+        "FRONTEGG_REGION",
+    ]);
+    env::remove_var("FRONTEGG_ENDPOINT");
+    env::remove_var("FRONTEGG_REGION");
+
+    // Verify missing variables are named in the error.
+    env::remove_var("FRONTEGG_CLIENT_ID");
+    env::set_var("FRONTEGG_SECRET_KEY", "secret");
+    match ClientConfig::from_env() {
+        Err(EnvError::Missing(name)) => assert_eq!(name, "FRONTEGG_CLIENT_ID"),
+        res => panic!("unexpected result: {res:?}"),
+    }
+    env::set_var("FRONTEGG_CLIENT_ID", "id");
+    env::remove_var("FRONTEGG_SECRET_KEY");
+    match Client::from_env() {
+        Err(e @ EnvError::Missing(_)) => {
+            assert!(e.to_string().contains("FRONTEGG_SECRET_KEY"), "{e}")
+        }
+        res => panic!("unexpected result: {res:?}"),
+    }
+
+    // Verify present variables are read.
+    env::set_var("FRONTEGG_SECRET_KEY", "secret");
+    let config = ClientConfig::from_env().unwrap();
+    assert_eq!(config.client_id, "id");
+    assert_eq!(config.secret_key.expose(), "secret");
+
+    // Verify the endpoint is read from the region.
+    env::set_var("FRONTEGG_REGION", "US");
+    let client = Client::from_env().unwrap();
+    assert!(format!("{client:?}").contains("api.us.frontegg.com"));
+    env::set_var("FRONTEGG_REGION", "mars");
+    match Client::from_env() {
+        Err(EnvError::Invalid { name, .. }) => assert_eq!(name, "FRONTEGG_REGION"),
+        res => panic!("unexpected result: {res:?}"),
+    }
+
+    // Verify the endpoint takes precedence over the region.
+    let server = MockServer::start().await;
+    let mock = Mock::given(matchers::path("/auth/vendor"))
+        .and(matchers::method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("{\"token\":\"test\", \"expiresIn\":3600}"),
+        );
+    server.register(mock).await;
+    let mock = Mock::given(matchers::method("GET"))
+        .and(matchers::path("/tenants/resources/tenants/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1);
+    server.register(mock).await;
+    env::set_var("FRONTEGG_ENDPOINT", server.uri());
+    let client = Client::from_env().unwrap();
+    assert!(client.list_tenants().await.unwrap().is_empty());
+
+    env::set_var("FRONTEGG_ENDPOINT", "not a url");
+    match Client::from_env() {
+        Err(EnvError::Invalid { name, .. }) => assert_eq!(name, "FRONTEGG_ENDPOINT"),
+        res => panic!("unexpected result: {res:?}"),
+    }
+}